@@ -0,0 +1,65 @@
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn encode(buffer: &[u8]) -> String {
+    let mut result = String::new();
+
+    for chunk in buffer.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, byte)| {
+            bits | (*byte as u32) << (16 - 8 * i)
+        });
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+
+    result
+}
+
+// Both the standard and the URL-safe alphabet are accepted, with or without
+// padding. Whitespace, as in wrapped MIME output, is skipped.
+pub fn decode(source: &str) -> Option<Vec<u8>> {
+    let mut result = vec![];
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+    let mut padding = 0;
+
+    for ch in source.chars().filter(|ch| !ch.is_whitespace()) {
+        let value = match ch {
+            'A'..='Z' => ch as u32 - 'A' as u32,
+            'a'..='z' => ch as u32 - 'a' as u32 + 26,
+            '0'..='9' => ch as u32 - '0' as u32 + 52,
+            '+' | '-' => 62,
+            '/' | '_' => 63,
+            '=' => {
+                padding += 1;
+                continue;
+            }
+            _ => return None,
+        };
+
+        if padding > 0 {
+            return None;
+        }
+
+        bits = bits << 6 | value;
+        bit_count += 6;
+
+        if bit_count >= 8 {
+            bit_count -= 8;
+            result.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+
+    // A single character left over can't hold a whole byte.
+    if bit_count == 6 || padding > 2 {
+        None
+    } else {
+        Some(result)
+    }
+}
@@ -1,28 +1,399 @@
-use crate::error::ProtodecError;
-use std::num::ParseIntError;
+use crate::{base64, error::ProtodecError, hex};
 
 pub fn load_test_proto_object() -> Result<Vec<u8>, ProtodecError> {
     panic!();
 }
 
-pub fn parse_u8_vec_from_string_with_integers<T: Into<String>>(
-    source: T,
-) -> Result<std::vec::Vec<u8>, ParseIntError> {
+// Comma separated decimals are the original input format. Literals pasted
+// from code are recognized by their quotes or brackets. Anything else is read
+// as hex, and as base64 when it isn't valid hex.
+pub fn parse_u8_vec_from_string<T: Into<String>>(source: T) -> Result<Vec<u8>, ProtodecError> {
     let source = source.into();
-    let mut source = source.trim_end().to_string();
 
-    if let Some(ch) = source.chars().last() {
-        if ch == ',' {
-            let source_without_last_comma = &source[..(source.len() - 1)];
-            source = source_without_last_comma.to_string();
+    if source.contains(&['"', '\''][..]) {
+        return parse_u8_vec_from_string_literals(&source);
+    }
+
+    if source.contains(&['[', '{'][..]) {
+        return parse_u8_vec_from_array_literal(&source);
+    }
+
+    if is_decimal_list(&source) {
+        return parse_u8_vec_from_decimal_string(&source);
+    }
+
+    parse_u8_vec_from_hex_or_base64_string(&source)
+}
+
+// Hex digits are valid base64 too, so text that is valid as both is read as
+// hex. The base64 error is only shown for text with punctuation hex never
+// has, otherwise the hex error points at the mistyped character.
+fn parse_u8_vec_from_hex_or_base64_string(source: &str) -> Result<Vec<u8>, ProtodecError> {
+    let hex_error = match parse_u8_vec_from_hex_string(source) {
+        Ok(buffer) => return Ok(buffer),
+        Err(err) => err,
+    };
+
+    if source.trim().is_empty() {
+        return Err(hex_error);
+    }
+
+    match parse_u8_vec_from_base64_string(source) {
+        Ok(buffer) => Ok(buffer),
+        Err(err) if source.contains(&['+', '/', '-', '_', '='][..]) => Err(err),
+        Err(_) => Err(hex_error),
+    }
+}
+
+// Both base64 and base64url are accepted, with or without padding, and
+// line breaks are skipped.
+pub fn parse_u8_vec_from_base64_string(source: &str) -> Result<Vec<u8>, ProtodecError> {
+    if let Some(buffer) = base64::decode(source) {
+        return Ok(buffer);
+    }
+
+    let padding_offset = source.find('=').unwrap_or(source.len());
+
+    match source[padding_offset..]
+        .char_indices()
+        .find(|(_, ch)| !ch.is_whitespace() && *ch != '=')
+    {
+        Some((index, ch)) => Err(error_at(
+            source,
+            padding_offset + index,
+            format!("Unexpected character '{}' after padding", ch),
+        )),
+        None if source.matches('=').count() > 2 => Err(error_at(
+            source,
+            padding_offset,
+            "Too much padding".to_string(),
+        )),
+        None => Err(ProtodecError {
+            message: "The base64 input is one character too long or too short".to_string(),
+        }),
+    }
+}
+
+// A list with commas is decimal unless it has hex letters, like "0a,08". A
+// lone number that fits in a byte is decimal as well.
+fn is_decimal_list(source: &str) -> bool {
+    let source = source.trim();
+
+    if source.contains(',') {
+        !source.contains("0x")
+            && !source.contains("0X")
+            && !source
+                .chars()
+                .any(|ch| ch.is_ascii_hexdigit() && !ch.is_ascii_digit())
+    } else {
+        source.parse::<u8>().is_ok()
+    }
+}
+
+// Items are separated by commas, with one trailing comma allowed.
+pub fn parse_u8_vec_from_decimal_string(source: &str) -> Result<Vec<u8>, ProtodecError> {
+    let mut buffer = vec![];
+    let mut offset = 0;
+    let items: Vec<&str> = source.split(',').collect();
+
+    for (index, item) in items.iter().enumerate() {
+        let token = item.trim();
+        let token_offset = offset + (item.len() - item.trim_start().len());
+        offset += item.len() + 1;
+
+        if token.is_empty() && index + 1 == items.len() && index > 0 {
+            break;
+        }
+
+        match token.parse::<u8>() {
+            Ok(byte) => buffer.push(byte),
+            Err(_) if token.is_empty() => {
+                return Err(error_at(source, token_offset, "Missing byte".to_string()))
+            }
+            Err(_) => {
+                return Err(error_at(
+                    source,
+                    token_offset,
+                    format!("{} is not a decimal byte", token),
+                ))
+            }
+        }
+    }
+
+    Ok(buffer)
+}
+
+// Accepts contiguous digits, bytes separated by spaces, colons or commas,
+// and 0x prefixed tokens, in any case.
+pub fn parse_u8_vec_from_hex_string(source: &str) -> Result<Vec<u8>, ProtodecError> {
+    let mut buffer = vec![];
+
+    for (offset, token) in split_tokens(source, |ch| ch.is_whitespace() || ch == ':' || ch == ',') {
+        let (digits_offset, digits, prefixed) =
+            if token.starts_with("0x") || token.starts_with("0X") {
+                (offset + 2, &token[2..], true)
+            } else {
+                (offset, token, false)
+            };
+
+        if let Some((index, ch)) = digits
+            .char_indices()
+            .find(|(_, ch)| !ch.is_ascii_hexdigit())
+        {
+            return Err(error_at(
+                source,
+                digits_offset + index,
+                format!("Unexpected character '{}'", ch),
+            ));
+        }
+
+        if prefixed && (digits.is_empty() || digits.len() > 2) {
+            return Err(error_at(
+                source,
+                offset,
+                format!("{} is not a single byte", token),
+            ));
+        }
+
+        if !prefixed && digits.len() & 1 == 1 {
+            return Err(error_at(
+                source,
+                offset,
+                format!("Odd number of hex digits in {}", token),
+            ));
+        }
+
+        if prefixed {
+            buffer.extend(hex::decode_byte(digits));
+        } else {
+            buffer.extend(hex::decode(digits).unwrap_or_default());
+        }
+    }
+
+    if buffer.is_empty() {
+        return Err(ProtodecError {
+            message: "The input is empty".to_string(),
+        });
+    }
+
+    Ok(buffer)
+}
+
+// Reads the contents of every quoted string, so C strings split over lines
+// and Python or Rust prefixes like b'...' work. Text around the strings,
+// like an assignment, is skipped.
+pub fn parse_u8_vec_from_string_literals(source: &str) -> Result<Vec<u8>, ProtodecError> {
+    let mut buffer = vec![];
+    let mut chars = source.char_indices().peekable();
+    let mut quote = None;
+
+    while let Some((offset, ch)) = chars.next() {
+        match (quote, ch) {
+            (None, '"') | (None, '\'') => quote = Some((offset, ch)),
+            (None, _) => {}
+            (Some((_, quote_char)), _) if ch == quote_char => quote = None,
+            (Some(_), '\\') => take_escape(source, offset, &mut chars, &mut buffer)?,
+            (Some(_), _) => {
+                let mut encoded = [0; 4];
+                buffer.extend(ch.encode_utf8(&mut encoded).as_bytes());
+            }
         }
     }
 
-    source
-        .replace(' ', "")
-        .replace('\n', "")
-        .replace('\t', "")
-        .split(',')
-        .map(|x| x.parse::<u8>())
+    if let Some((offset, _)) = quote {
+        return Err(error_at(source, offset, "Unterminated string".to_string()));
+    }
+
+    Ok(buffer)
+}
+
+fn take_escape(
+    source: &str,
+    offset: usize,
+    chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
+    buffer: &mut Vec<u8>,
+) -> Result<(), ProtodecError> {
+    let escape = match chars.next() {
+        Some((_, escape)) => escape,
+        None => return Err(error_at(source, offset, "Unterminated escape".to_string())),
+    };
+
+    let byte = match escape {
+        'n' => b'\n',
+        'r' => b'\r',
+        't' => b'\t',
+        'a' => 0x07,
+        'b' => 0x08,
+        'f' => 0x0c,
+        'v' => 0x0b,
+        'e' => 0x1b,
+        '\\' | '\'' | '"' | '?' => escape as u8,
+        'x' => {
+            let digits = take_digits(chars, 16, 2);
+            if digits.is_empty() {
+                return Err(error_at(
+                    source,
+                    offset,
+                    "Missing hex digits in \\x".to_string(),
+                ));
+            }
+            hex::decode_byte(&digits).unwrap_or_default()
+        }
+        '0'..='7' => {
+            let mut digits = escape.to_string();
+            digits.push_str(&take_digits(chars, 8, 2));
+            match u8::from_str_radix(&digits, 8) {
+                Ok(byte) => byte,
+                Err(_) => {
+                    return Err(error_at(
+                        source,
+                        offset,
+                        format!("Octal escape \\{} is not a single byte", digits),
+                    ))
+                }
+            }
+        }
+        'u' => {
+            let braced = chars.peek().map(|(_, ch)| *ch) == Some('{');
+            let digits = if braced {
+                chars.next();
+                let digits = take_digits(chars, 16, 6);
+                if chars.next().map(|(_, ch)| ch) != Some('}') {
+                    return Err(error_at(source, offset, "Unterminated \\u{".to_string()));
+                }
+                digits
+            } else {
+                take_digits(chars, 16, 4)
+            };
+
+            match u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(std::char::from_u32)
+            {
+                Some(ch) => {
+                    let mut encoded = [0; 4];
+                    buffer.extend(ch.encode_utf8(&mut encoded).as_bytes());
+                    return Ok(());
+                }
+                None => {
+                    return Err(error_at(
+                        source,
+                        offset,
+                        "Invalid unicode escape".to_string(),
+                    ))
+                }
+            }
+        }
+        _ => {
+            return Err(error_at(
+                source,
+                offset,
+                format!("Unknown escape \\{}", escape),
+            ))
+        }
+    };
+
+    buffer.push(byte);
+    Ok(())
+}
+
+fn take_digits(
+    chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
+    radix: u32,
+    max_count: usize,
+) -> String {
+    let mut digits = String::new();
+
+    while digits.len() < max_count {
+        match chars.peek() {
+            Some((_, ch)) if ch.is_digit(radix) => {
+                digits.push(*ch);
+                chars.next();
+            }
+            _ => break,
+        }
+    }
+
+    digits
+}
+
+// Reads the items between the last opening bracket and the bracket that
+// closes it, as in Go []byte{...}, Java new byte[]{...} or Rust &[...].
+// Items may be signed, hex, octal or binary, with Rust suffixes and Java
+// (byte) casts.
+pub fn parse_u8_vec_from_array_literal(source: &str) -> Result<Vec<u8>, ProtodecError> {
+    let start = source.rfind(&['[', '{'][..]).unwrap_or(0) + 1;
+    let end = match source[start..].find(&[']', '}'][..]) {
+        Some(length) => start + length,
+        None => return Err(error_at(source, start - 1, "Unclosed bracket".to_string())),
+    };
+
+    split_tokens(&source[start..end], |ch| ch == ',')
+        .into_iter()
+        .filter(|(_, token)| !token.trim().is_empty())
+        .map(|(offset, token)| {
+            let offset = start + offset + (token.len() - token.trim_start().len());
+            parse_array_item(token.trim())
+                .ok_or_else(|| error_at(source, offset, format!("{} is not a byte", token.trim())))
+        })
         .collect()
 }
+
+fn parse_array_item(item: &str) -> Option<u8> {
+    let item = item.trim_start_matches("(byte)").trim_start();
+    let item = item.trim_end_matches("u8").trim_end_matches("i8");
+    let negative = item.starts_with('-');
+    let digits = &item[negative as usize..];
+
+    let value = if digits.starts_with("0x") || digits.starts_with("0X") {
+        i64::from_str_radix(&digits[2..], 16)
+    } else if digits.starts_with("0o") || digits.starts_with("0O") {
+        i64::from_str_radix(&digits[2..], 8)
+    } else if digits.starts_with("0b") || digits.starts_with("0B") {
+        i64::from_str_radix(&digits[2..], 2)
+    } else {
+        digits.parse::<i64>()
+    }
+    .ok()?;
+
+    // Signed bytes, as in Java, are stored in two's complement.
+    match if negative { -value } else { value } {
+        value @ -128..=-1 => Some(value as i8 as u8),
+        value @ 0..=255 => Some(value as u8),
+        _ => None,
+    }
+}
+
+// Returns the tokens between separators with their byte offsets.
+fn split_tokens(source: &str, is_separator: impl Fn(char) -> bool) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut start = None;
+
+    for (offset, ch) in source.char_indices() {
+        match (is_separator(ch), start) {
+            (true, Some(token_start)) => {
+                tokens.push((token_start, &source[token_start..offset]));
+                start = None;
+            }
+            (false, None) => start = Some(offset),
+            _ => {}
+        }
+    }
+
+    if let Some(token_start) = start {
+        tokens.push((token_start, &source[token_start..]));
+    }
+
+    tokens
+}
+
+// Positions are counted in characters from 1, as a user sees them.
+fn error_at(source: &str, offset: usize, message: String) -> ProtodecError {
+    ProtodecError {
+        message: format!(
+            "{} at position {}",
+            message,
+            source[..offset].chars().count() + 1
+        ),
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{
+    document::{DocumentValue, Reader, MAX_DEPTH},
+    error::ProtodecError,
+};
+
+const BREAK: u8 = 0xff;
+
+pub fn decode(buffer: &[u8]) -> Result<DocumentValue, ProtodecError> {
+    let mut reader = Reader::new(buffer);
+    let value = take_value(&mut reader, 0)?;
+
+    reader.finish(value)
+}
+
+fn take_value(reader: &mut Reader<'_>, depth: usize) -> Result<DocumentValue, ProtodecError> {
+    if depth > MAX_DEPTH {
+        return Err(reader.error("Nesting is too deep".to_string()));
+    }
+
+    let initial_byte = reader.take_byte()?;
+    let major_type = initial_byte >> 5;
+    let additional_info = initial_byte & 0x1f;
+
+    if additional_info == 31 {
+        return take_indefinite(reader, major_type, depth);
+    }
+
+    if major_type == 7 {
+        return take_simple_or_float(reader, additional_info);
+    }
+
+    let argument = take_argument(reader, additional_info)?;
+
+    match major_type {
+        0 => Ok(DocumentValue::Integer(argument as i128)),
+        1 => Ok(DocumentValue::Integer(-1 - argument as i128)),
+        2 => {
+            let length = reader.check_length(argument)?;
+            Ok(DocumentValue::Bytes(reader.take(length)?.to_vec()))
+        }
+        3 => {
+            let length = reader.check_length(argument)?;
+            Ok(DocumentValue::Text(reader.take_text(length)?))
+        }
+        4 => {
+            let length = reader.check_length(argument)?;
+            let items = (0..length)
+                .map(|_| take_value(reader, depth + 1))
+                .collect::<Result<_, _>>()?;
+            Ok(DocumentValue::Array(items))
+        }
+        5 => {
+            let length = reader.check_length(argument)?;
+            let entries = (0..length)
+                .map(|_| take_entry(reader, depth))
+                .collect::<Result<_, _>>()?;
+            Ok(DocumentValue::Map(entries))
+        }
+        _ => Ok(DocumentValue::Tagged(
+            argument,
+            Box::new(take_value(reader, depth + 1)?),
+        )),
+    }
+}
+
+fn take_argument(reader: &mut Reader<'_>, additional_info: u8) -> Result<u64, ProtodecError> {
+    match additional_info {
+        0..=23 => Ok(additional_info as u64),
+        24 => reader.take_uint(1),
+        25 => reader.take_uint(2),
+        26 => reader.take_uint(4),
+        27 => reader.take_uint(8),
+        _ => Err(reader.error(format!("Reserved additional info {}", additional_info))),
+    }
+}
+
+fn take_entry(
+    reader: &mut Reader<'_>,
+    depth: usize,
+) -> Result<(DocumentValue, DocumentValue), ProtodecError> {
+    let key = take_value(reader, depth + 1)?;
+    let value = take_value(reader, depth + 1)?;
+
+    Ok((key, value))
+}
+
+fn is_break(reader: &mut Reader<'_>) -> Result<bool, ProtodecError> {
+    if reader.peek_byte()? == BREAK {
+        reader.take_byte()?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+fn take_indefinite(
+    reader: &mut Reader<'_>,
+    major_type: u8,
+    depth: usize,
+) -> Result<DocumentValue, ProtodecError> {
+    match major_type {
+        2 | 3 => {
+            let mut bytes = vec![];
+
+            while !is_break(reader)? {
+                match take_value(reader, depth + 1)? {
+                    DocumentValue::Bytes(chunk) if major_type == 2 => bytes.extend(chunk),
+                    DocumentValue::Text(chunk) if major_type == 3 => {
+                        bytes.extend(chunk.into_bytes())
+                    }
+                    _ => {
+                        return Err(reader.error(
+                            "Indefinite length string holds a chunk of another type".to_string(),
+                        ))
+                    }
+                }
+            }
+
+            if major_type == 2 {
+                Ok(DocumentValue::Bytes(bytes))
+            } else {
+                // Every chunk is valid UTF-8 on its own, so the whole is too.
+                Ok(DocumentValue::Text(
+                    String::from_utf8(bytes).unwrap_or_default(),
+                ))
+            }
+        }
+        4 => {
+            let mut items = vec![];
+
+            while !is_break(reader)? {
+                items.push(take_value(reader, depth + 1)?);
+            }
+
+            Ok(DocumentValue::Array(items))
+        }
+        5 => {
+            let mut entries = vec![];
+
+            while !is_break(reader)? {
+                entries.push(take_entry(reader, depth)?);
+            }
+
+            Ok(DocumentValue::Map(entries))
+        }
+        7 => Err(reader.error("Unexpected break".to_string())),
+        _ => Err(reader.error(format!(
+            "Major type {} can't have indefinite length",
+            major_type
+        ))),
+    }
+}
+
+fn take_simple_or_float(
+    reader: &mut Reader<'_>,
+    additional_info: u8,
+) -> Result<DocumentValue, ProtodecError> {
+    match additional_info {
+        20 => Ok(DocumentValue::Bool(false)),
+        21 => Ok(DocumentValue::Bool(true)),
+        22 => Ok(DocumentValue::Null),
+        23 => Ok(DocumentValue::Undefined),
+        24 => Ok(DocumentValue::Simple(reader.take_byte()?)),
+        25 => Ok(DocumentValue::Float(decode_half(
+            reader.take_uint(2)? as u16
+        ))),
+        26 => Ok(DocumentValue::Float(
+            f32::from_bits(reader.take_uint(4)? as u32) as f64,
+        )),
+        27 => Ok(DocumentValue::Float(f64::from_bits(reader.take_uint(8)?))),
+        0..=19 => Ok(DocumentValue::Simple(additional_info)),
+        _ => Err(reader.error(format!("Reserved additional info {}", additional_info))),
+    }
+}
+
+fn decode_half(bits: u16) -> f64 {
+    let sign = if bits >> 15 == 1 { -1.0 } else { 1.0 };
+    let exponent = (bits >> 10 & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f64;
+
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f64.powi(exponent - 15),
+    }
+}
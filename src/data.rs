@@ -29,7 +29,7 @@ pub fn take_data(key: Key, buffer: &[u8]) -> Result<(Data, &[u8]), ProtodecError
     let field_number = key.field_number;
     match key.wire_type {
         WireType::Varint => {
-            let (varint, rest) = varint::take_varint(buffer);
+            let (varint, rest) = varint::take_varint(buffer)?;
             Ok((
                 Varint {
                     field_number,
@@ -59,7 +59,17 @@ pub fn take_data(key: Key, buffer: &[u8]) -> Result<(Data, &[u8]), ProtodecError
             ))
         }
         WireType::LengthDelimited => {
-            let (buffer_size, rest) = varint::take_varint(buffer);
+            let (buffer_size, rest) = varint::take_varint(buffer)?;
+            if (rest.len() as u128) < buffer_size {
+                return Err(ProtodecError {
+                    message: format!(
+                        "Error. Buffer length {} can't be less than {} here.",
+                        rest.len(),
+                        buffer_size
+                    ),
+                });
+            }
+
             Ok((
                 Chunk {
                     field_number,
@@ -10,7 +10,7 @@ pub fn decode_message(buffer: &[u8]) -> Result<Vec<Data>, ProtodecError> {
     let mut buf = buffer;
 
     loop {
-        let (key, rest) = key::take_key(buf);
+        let (key, rest) = key::take_key(buf)?;
 
         let (data, rest) = data::take_data(key, rest)?;
 
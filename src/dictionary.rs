@@ -0,0 +1,280 @@
+use crate::{error::ProtodecError, proto_type::ProtoType};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldEntry {
+    pub name: Option<String>,
+    pub proto_type: Option<ProtoType>,
+    pub enum_labels: BTreeMap<i64, String>,
+    pub bit_names: BTreeMap<u32, String>,
+    pub note: Option<String>,
+}
+
+impl FieldEntry {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.proto_type.is_none()
+            && self.enum_labels.is_empty()
+            && self.bit_names.is_empty()
+            && self.note.is_none()
+    }
+
+    fn to_json(&self) -> Value {
+        let mut object = Map::new();
+
+        if let Some(name) = &self.name {
+            object.insert("name".into(), Value::String(name.clone()));
+        }
+
+        if let Some(proto_type) = self.proto_type {
+            object.insert("type".into(), Value::String(proto_type.to_string().into()));
+        }
+
+        if !self.enum_labels.is_empty() {
+            let labels = self
+                .enum_labels
+                .iter()
+                .map(|(value, label)| (value.to_string(), Value::String(label.clone())))
+                .collect();
+            object.insert("enum".into(), Value::Object(labels));
+        }
+
+        if !self.bit_names.is_empty() {
+            let names = self
+                .bit_names
+                .iter()
+                .map(|(bit, name)| (bit.to_string(), Value::String(name.clone())))
+                .collect();
+            object.insert("flags".into(), Value::Object(names));
+        }
+
+        if let Some(note) = &self.note {
+            object.insert("note".into(), Value::String(note.clone()));
+        }
+
+        Value::Object(object)
+    }
+
+    pub fn get_enum_label(&self, value: u128) -> Option<&str> {
+        self.enum_labels
+            .get(&(value as u64 as i64))
+            .map(|label| label.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FieldDictionary {
+    pub entries: BTreeMap<Vec<u128>, FieldEntry>,
+}
+
+impl FieldDictionary {
+    pub fn parse(source: &str) -> Result<Self, ProtodecError> {
+        let value: Value = serde_json::from_str(source)?;
+
+        let object = value.as_object().ok_or_else(|| ProtodecError {
+            message: "Error. Dictionary must be a JSON object keyed by field path.".into(),
+        })?;
+
+        let mut entries = BTreeMap::new();
+        for (path, value) in object {
+            let entry = match value {
+                Value::String(shorthand) => parse_shorthand(path, shorthand)?,
+                Value::Object(object) => parse_object(path, object)?,
+                _ => {
+                    return Err(ProtodecError {
+                        message: format!(
+                            "Error. Field {} must be described by a string or an object.",
+                            path
+                        ),
+                    })
+                }
+            };
+
+            entries.insert(parse_field_path(path)?, entry);
+        }
+
+        Ok(FieldDictionary { entries })
+    }
+
+    pub fn get(&self, path: &[u128]) -> Option<&FieldEntry> {
+        self.entries.get(path)
+    }
+
+    pub fn set(&mut self, path: Vec<u128>, entry: FieldEntry) {
+        if entry.is_empty() {
+            self.entries.remove(&path);
+        } else {
+            self.entries.insert(path, entry);
+        }
+    }
+
+    pub fn merge(&mut self, other: FieldDictionary) {
+        self.entries.extend(other.entries);
+    }
+
+    pub fn to_json(&self) -> String {
+        let object: Map<String, Value> = self
+            .entries
+            .iter()
+            .map(|(path, entry)| (format_field_path(path), entry.to_json()))
+            .collect();
+
+        serde_json::to_string_pretty(&Value::Object(object)).unwrap()
+    }
+}
+
+pub fn format_field_path(path: &[u128]) -> String {
+    path.iter()
+        .map(|field_number| field_number.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+pub fn parse_field_path(source: &str) -> Result<Vec<u128>, ProtodecError> {
+    source
+        .trim()
+        .split('.')
+        .map(|field_number| {
+            field_number
+                .trim()
+                .parse::<u128>()
+                .map_err(|_| ProtodecError {
+                    message: format!("Error. Field path `{}` is invalid.", source),
+                })
+        })
+        .collect()
+}
+
+pub fn format_labels<K: Display>(labels: &BTreeMap<K, String>) -> String {
+    labels
+        .iter()
+        .map(|(value, label)| format!("{}={}", value, label))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn parse_enum_labels(source: &str) -> Result<BTreeMap<i64, String>, ProtodecError> {
+    parse_labels(source, "Enum label", "2=WORK")
+}
+
+pub fn parse_bit_names(source: &str) -> Result<BTreeMap<u32, String>, ProtodecError> {
+    parse_labels(source, "Bit name", "0=READ")
+}
+
+fn parse_labels<K: FromStr + Ord>(
+    source: &str,
+    kind: &str,
+    example: &str,
+) -> Result<BTreeMap<K, String>, ProtodecError> {
+    let mut labels = BTreeMap::new();
+
+    for item in source.split(',').filter(|item| !item.trim().is_empty()) {
+        let mut parts = item.splitn(2, '=');
+        let value = parts.next().unwrap_or_default().trim();
+        let label = parts.next().unwrap_or_default().trim();
+
+        match value.parse::<K>() {
+            Ok(value) if !label.is_empty() => {
+                labels.insert(value, label.to_string());
+            }
+            _ => {
+                return Err(ProtodecError {
+                    message: format!(
+                        "Error. {} `{}` must look like `{}`.",
+                        kind,
+                        item.trim(),
+                        example
+                    ),
+                })
+            }
+        }
+    }
+
+    Ok(labels)
+}
+
+fn parse_shorthand(path: &str, shorthand: &str) -> Result<FieldEntry, ProtodecError> {
+    let mut parts = shorthand.splitn(2, ':');
+    let name = parts.next().unwrap_or_default().trim();
+    let proto_type = match parts.next() {
+        Some(proto_type) => Some(parse_proto_type(path, proto_type.trim())?),
+        None => None,
+    };
+
+    Ok(FieldEntry {
+        name: non_empty(name),
+        proto_type,
+        enum_labels: BTreeMap::new(),
+        bit_names: BTreeMap::new(),
+        note: None,
+    })
+}
+
+fn parse_object(path: &str, object: &Map<String, Value>) -> Result<FieldEntry, ProtodecError> {
+    let name = object
+        .get("name")
+        .and_then(Value::as_str)
+        .and_then(non_empty);
+
+    let proto_type = match object.get("type").and_then(Value::as_str) {
+        Some(proto_type) => Some(parse_proto_type(path, proto_type)?),
+        None => None,
+    };
+
+    let mut enum_labels = BTreeMap::new();
+    if let Some(labels) = object.get("enum").and_then(Value::as_object) {
+        for (value, label) in labels {
+            let value = value.trim().parse::<i64>().map_err(|_| ProtodecError {
+                message: format!(
+                    "Error. Enum value `{}` of field {} is invalid.",
+                    value, path
+                ),
+            })?;
+            if let Some(label) = label.as_str() {
+                enum_labels.insert(value, label.to_string());
+            }
+        }
+    }
+
+    let mut bit_names = BTreeMap::new();
+    if let Some(names) = object.get("flags").and_then(Value::as_object) {
+        for (bit, name) in names {
+            let bit = bit.trim().parse::<u32>().map_err(|_| ProtodecError {
+                message: format!("Error. Flag bit `{}` of field {} is invalid.", bit, path),
+            })?;
+            if let Some(name) = name.as_str() {
+                bit_names.insert(bit, name.to_string());
+            }
+        }
+    }
+
+    let note = object
+        .get("note")
+        .and_then(Value::as_str)
+        .and_then(non_empty);
+
+    Ok(FieldEntry {
+        name,
+        proto_type,
+        enum_labels,
+        bit_names,
+        note,
+    })
+}
+
+fn parse_proto_type(path: &str, proto_type: &str) -> Result<ProtoType, ProtodecError> {
+    ProtoType::parse(proto_type).ok_or_else(|| ProtodecError {
+        message: format!("Error. Type `{}` of field {} is unknown.", proto_type, path),
+    })
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
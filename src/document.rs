@@ -0,0 +1,154 @@
+use crate::{error::ProtodecError, hex};
+
+// Nesting deeper than this is reported as an error instead of overflowing
+// the stack on hostile input.
+pub const MAX_DEPTH: usize = 64;
+
+// A value of a self-describing binary format, like CBOR or MessagePack.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DocumentValue {
+    Null,
+    Undefined,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+    Array(Vec<DocumentValue>),
+    Map(Vec<(DocumentValue, DocumentValue)>),
+    Tagged(u64, Box<DocumentValue>),
+    Extension(i8, Vec<u8>),
+    Simple(u8),
+}
+
+impl DocumentValue {
+    pub fn get_type_name(&self) -> String {
+        match self {
+            DocumentValue::Null => "null".to_string(),
+            DocumentValue::Undefined => "undefined".to_string(),
+            DocumentValue::Bool(_) => "bool".to_string(),
+            DocumentValue::Integer(_) => "integer".to_string(),
+            DocumentValue::Float(_) => "float".to_string(),
+            DocumentValue::Text(_) => "text".to_string(),
+            DocumentValue::Bytes(bytes) => format!("bytes {}", bytes.len()),
+            DocumentValue::Array(items) => format!("array {}", items.len()),
+            DocumentValue::Map(entries) => format!("map {}", entries.len()),
+            DocumentValue::Tagged(tag, _) => format!("tag {}", tag),
+            DocumentValue::Extension(extension_type, _) => format!("ext {}", extension_type),
+            DocumentValue::Simple(_) => "simple".to_string(),
+        }
+    }
+
+    // A compact one line form, close to the CBOR diagnostic notation.
+    pub fn format(&self) -> String {
+        match self {
+            DocumentValue::Null => "null".to_string(),
+            DocumentValue::Undefined => "undefined".to_string(),
+            DocumentValue::Bool(value) => value.to_string(),
+            DocumentValue::Integer(value) => value.to_string(),
+            DocumentValue::Float(value) => format!("{:?}", value),
+            DocumentValue::Text(value) => format!("{:?}", value),
+            DocumentValue::Bytes(bytes) => format!("h'{}'", hex::encode(bytes)),
+            DocumentValue::Array(items) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(|item| item.format())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            DocumentValue::Map(entries) => format!(
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key.format(), value.format()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            DocumentValue::Tagged(tag, value) => format!("{}({})", tag, value.format()),
+            DocumentValue::Extension(extension_type, data) => {
+                format!("ext({}, h'{}')", extension_type, hex::encode(data))
+            }
+            DocumentValue::Simple(value) => format!("simple({})", value),
+        }
+    }
+}
+
+pub struct Reader<'a> {
+    buffer: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(buffer: &'a [u8]) -> Self {
+        Reader { buffer, offset: 0 }
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.buffer.len()
+    }
+
+    pub fn take(&mut self, count: usize) -> Result<&'a [u8], ProtodecError> {
+        if self.buffer.len() - self.offset < count {
+            return Err(self.error(format!(
+                "Unexpected end of data, {} more bytes expected",
+                count
+            )));
+        }
+
+        let bytes = &self.buffer[self.offset..self.offset + count];
+        self.offset += count;
+        Ok(bytes)
+    }
+
+    pub fn take_byte(&mut self) -> Result<u8, ProtodecError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn peek_byte(&self) -> Result<u8, ProtodecError> {
+        match self.buffer.get(self.offset) {
+            Some(byte) => Ok(*byte),
+            None => Err(self.error("Unexpected end of data".to_string())),
+        }
+    }
+
+    pub fn take_uint(&mut self, count: usize) -> Result<u64, ProtodecError> {
+        Ok(self
+            .take(count)?
+            .iter()
+            .fold(0u64, |value, byte| value << 8 | *byte as u64))
+    }
+
+    pub fn take_text(&mut self, count: usize) -> Result<String, ProtodecError> {
+        let offset = self.offset;
+        let bytes = self.take(count)?;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| ProtodecError {
+            message: format!("Invalid UTF-8 text at byte {}", offset),
+        })
+    }
+
+    pub fn error(&self, message: String) -> ProtodecError {
+        ProtodecError {
+            message: format!("{} at byte {}", message, self.offset),
+        }
+    }
+
+    // Lengths come from the data itself, so they are checked against the
+    // rest of the buffer before anything is allocated for them.
+    pub fn check_length(&self, length: u64) -> Result<usize, ProtodecError> {
+        if length > (self.buffer.len() - self.offset) as u64 {
+            Err(self.error(format!("Length {} exceeds the data", length)))
+        } else {
+            Ok(length as usize)
+        }
+    }
+
+    pub fn finish(self, value: DocumentValue) -> Result<DocumentValue, ProtodecError> {
+        if self.is_at_end() {
+            Ok(value)
+        } else {
+            Err(self.error("Unexpected data after the value".to_string()))
+        }
+    }
+}
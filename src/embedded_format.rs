@@ -0,0 +1,201 @@
+use crate::base64;
+use serde_json::Value;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EmbeddedFormat {
+    Json,
+    Xml,
+    UrlEncoded,
+}
+
+impl EmbeddedFormat {
+    // Padded base64 has the characters of a form too, so text that decodes
+    // as base64 is never taken for a form.
+    pub fn detect(text: &str) -> Option<Self> {
+        let trimmed = text.trim();
+
+        if (trimmed.starts_with('{') || trimmed.starts_with('['))
+            && serde_json::from_str::<Value>(trimmed).is_ok()
+        {
+            Some(EmbeddedFormat::Json)
+        } else if trimmed.starts_with('<') && format_xml(trimmed).is_some() {
+            Some(EmbeddedFormat::Xml)
+        } else if parse_url_encoded(trimmed).is_some() && base64::decode(trimmed).is_none() {
+            Some(EmbeddedFormat::UrlEncoded)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_string(self) -> &'static str {
+        match self {
+            EmbeddedFormat::Json => "JSON",
+            EmbeddedFormat::Xml => "XML",
+            EmbeddedFormat::UrlEncoded => "form",
+        }
+    }
+}
+
+enum XmlToken<'a> {
+    Open(&'a str, &'a str),
+    Close(&'a str, &'a str),
+    Leaf(&'a str),
+    Text(&'a str),
+}
+
+fn tokenize_xml(source: &str) -> Option<Vec<XmlToken<'_>>> {
+    let mut tokens = vec![];
+    let mut rest = source;
+
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = rest[..end].trim();
+            if !text.is_empty() {
+                tokens.push(XmlToken::Text(text));
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        let terminator = if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<![CDATA[") {
+            "]]>"
+        } else {
+            ">"
+        };
+        let end = rest.find(terminator)? + terminator.len();
+        let tag = &rest[..end];
+        rest = &rest[end..];
+
+        if tag.starts_with("<!") || tag.starts_with("<?") || tag.ends_with("/>") {
+            tokens.push(XmlToken::Leaf(tag));
+        } else if tag.starts_with("</") {
+            tokens.push(XmlToken::Close(
+                tag,
+                get_tag_name(tag.trim_start_matches("</")),
+            ));
+        } else {
+            tokens.push(XmlToken::Open(
+                tag,
+                get_tag_name(tag.trim_start_matches('<')),
+            ));
+        }
+    }
+
+    Some(tokens)
+}
+
+fn get_tag_name(tag: &str) -> &str {
+    let end = tag
+        .find(|ch: char| ch.is_whitespace() || ch == '>' || ch == '/')
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+// Every element goes on its own line, except elements holding only text,
+// which stay on one line. Unbalanced tags mean the text is not XML.
+pub fn format_xml(source: &str) -> Option<String> {
+    let tokens = tokenize_xml(source)?;
+    let mut lines = vec![];
+    let mut open_names = vec![];
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let indent = "  ".repeat(open_names.len());
+
+        match &tokens[i] {
+            XmlToken::Open(tag, name) => {
+                if let (Some(XmlToken::Text(text)), Some(XmlToken::Close(close, close_name))) =
+                    (tokens.get(i + 1), tokens.get(i + 2))
+                {
+                    if name == close_name {
+                        lines.push(format!("{}{}{}{}", indent, tag, text, close));
+                        i += 3;
+                        continue;
+                    }
+                }
+
+                lines.push(format!("{}{}", indent, tag));
+                open_names.push(*name);
+            }
+            XmlToken::Close(tag, name) => {
+                if open_names.pop() != Some(*name) {
+                    return None;
+                }
+                lines.push(format!("{}{}", "  ".repeat(open_names.len()), tag));
+            }
+            XmlToken::Leaf(tag) => lines.push(format!("{}{}", indent, tag)),
+            XmlToken::Text(text) => lines.push(format!("{}{}", indent, text)),
+        }
+
+        i += 1;
+    }
+
+    let has_element = tokens.iter().any(|token| match token {
+        XmlToken::Open(..) => true,
+        XmlToken::Leaf(tag) => tag.ends_with("/>"),
+        _ => false,
+    });
+
+    if open_names.is_empty() && has_element {
+        Some(lines.join("\n"))
+    } else {
+        None
+    }
+}
+
+// Every pair needs a key and an equals sign. A value made only of equals
+// signs is base64 padding rather than a form value.
+pub fn parse_url_encoded(source: &str) -> Option<Vec<(String, String)>> {
+    let is_form_char = |ch: char| ch.is_ascii_alphanumeric() || "-._~%+=&*".contains(ch);
+
+    if !source.chars().all(is_form_char) {
+        return None;
+    }
+
+    source
+        .split('&')
+        .map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            let key = decode_url_component(parts.next().unwrap_or_default())?;
+            let value = parts.next()?;
+
+            if key.is_empty() || (!value.is_empty() && value.chars().all(|ch| ch == '=')) {
+                None
+            } else {
+                Some((key, decode_url_component(value)?))
+            }
+        })
+        .collect()
+}
+
+fn decode_url_component(source: &str) -> Option<String> {
+    let bytes = source.as_bytes();
+    let mut decoded = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = source.get(i + 1..i + 3)?;
+                if !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+                    return None;
+                }
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    Some(String::from_utf8_lossy(&decoded).into_owned())
+}
@@ -0,0 +1,50 @@
+// Shorter chunks can't show a meaningful distribution, as most of their
+// bytes are distinct anyway.
+const MIN_HIGH_ENTROPY_LENGTH: usize = 64;
+const HIGH_ENTROPY_RATIO: f64 = 0.9;
+pub const HISTOGRAM_BUCKET_COUNT: usize = 16;
+
+pub fn count_bytes(buffer: &[u8]) -> [usize; 256] {
+    let mut counts = [0; 256];
+
+    for byte in buffer {
+        counts[*byte as usize] += 1;
+    }
+
+    counts
+}
+
+// Shannon entropy in bits per byte, from 0 for a repeated byte to 8 for
+// evenly distributed bytes.
+pub fn get_entropy(buffer: &[u8]) -> f64 {
+    let length = buffer.len() as f64;
+
+    count_bytes(buffer)
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let probability = *count as f64 / length;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+// The entropy is compared with the most a buffer of this length can have,
+// which is less than 8 bits per byte for chunks shorter than 256 bytes.
+pub fn is_high_entropy(buffer: &[u8]) -> bool {
+    if buffer.len() < MIN_HIGH_ENTROPY_LENGTH {
+        return false;
+    }
+
+    let max_entropy = (buffer.len().min(256) as f64).log2();
+
+    get_entropy(buffer) >= HIGH_ENTROPY_RATIO * max_entropy
+}
+
+// Byte counts grouped into equal ranges of byte values.
+pub fn get_histogram(buffer: &[u8]) -> Vec<usize> {
+    count_bytes(buffer)
+        .chunks(256 / HISTOGRAM_BUCKET_COUNT)
+        .map(|counts| counts.iter().sum())
+        .collect()
+}
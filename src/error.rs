@@ -11,3 +11,11 @@ impl From<std::io::Error> for ProtodecError {
         }
     }
 }
+
+impl From<serde_json::Error> for ProtodecError {
+    fn from(err: serde_json::Error) -> Self {
+        ProtodecError {
+            message: err.to_string(),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::base64;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FileFormat {
+    Png,
+    Jpeg,
+    Gif,
+    WebP,
+    Bmp,
+    Pdf,
+    Zip,
+    Gzip,
+    SevenZip,
+    Wasm,
+    Elf,
+}
+
+impl FileFormat {
+    pub fn all() -> &'static [FileFormat] {
+        &[
+            FileFormat::Png,
+            FileFormat::Jpeg,
+            FileFormat::Gif,
+            FileFormat::WebP,
+            FileFormat::Bmp,
+            FileFormat::Pdf,
+            FileFormat::Zip,
+            FileFormat::Gzip,
+            FileFormat::SevenZip,
+            FileFormat::Wasm,
+            FileFormat::Elf,
+        ]
+    }
+
+    pub fn detect(buffer: &[u8]) -> Option<Self> {
+        FileFormat::all()
+            .iter()
+            .copied()
+            .find(|file_format| file_format.matches(buffer))
+    }
+
+    fn matches(self, buffer: &[u8]) -> bool {
+        match self {
+            FileFormat::Png => buffer.starts_with(b"\x89PNG\r\n\x1a\n"),
+            FileFormat::Jpeg => buffer.starts_with(b"\xff\xd8\xff"),
+            FileFormat::Gif => buffer.starts_with(b"GIF87a") || buffer.starts_with(b"GIF89a"),
+            FileFormat::WebP => {
+                buffer.len() >= 12 && buffer.starts_with(b"RIFF") && &buffer[8..12] == b"WEBP"
+            }
+            FileFormat::Bmp => buffer.len() >= 14 && buffer.starts_with(b"BM"),
+            FileFormat::Pdf => buffer.starts_with(b"%PDF-"),
+            FileFormat::Zip => buffer.starts_with(b"PK\x03\x04"),
+            FileFormat::Gzip => buffer.starts_with(b"\x1f\x8b\x08"),
+            FileFormat::SevenZip => buffer.starts_with(b"7z\xbc\xaf\x27\x1c"),
+            FileFormat::Wasm => buffer.starts_with(b"\0asm"),
+            FileFormat::Elf => buffer.starts_with(b"\x7fELF"),
+        }
+    }
+
+    pub fn to_string(self) -> &'static str {
+        match self {
+            FileFormat::Png => "PNG image",
+            FileFormat::Jpeg => "JPEG image",
+            FileFormat::Gif => "GIF image",
+            FileFormat::WebP => "WebP image",
+            FileFormat::Bmp => "BMP image",
+            FileFormat::Pdf => "PDF document",
+            FileFormat::Zip => "ZIP archive",
+            FileFormat::Gzip => "gzip archive",
+            FileFormat::SevenZip => "7z archive",
+            FileFormat::Wasm => "WebAssembly module",
+            FileFormat::Elf => "ELF executable",
+        }
+    }
+
+    pub fn get_extension(self) -> &'static str {
+        match self {
+            FileFormat::Png => "png",
+            FileFormat::Jpeg => "jpg",
+            FileFormat::Gif => "gif",
+            FileFormat::WebP => "webp",
+            FileFormat::Bmp => "bmp",
+            FileFormat::Pdf => "pdf",
+            FileFormat::Zip => "zip",
+            FileFormat::Gzip => "gz",
+            FileFormat::SevenZip => "7z",
+            FileFormat::Wasm => "wasm",
+            FileFormat::Elf => "elf",
+        }
+    }
+
+    pub fn get_mime_type(self) -> &'static str {
+        match self {
+            FileFormat::Png => "image/png",
+            FileFormat::Jpeg => "image/jpeg",
+            FileFormat::Gif => "image/gif",
+            FileFormat::WebP => "image/webp",
+            FileFormat::Bmp => "image/bmp",
+            FileFormat::Pdf => "application/pdf",
+            FileFormat::Zip => "application/zip",
+            FileFormat::Gzip => "application/gzip",
+            FileFormat::SevenZip => "application/x-7z-compressed",
+            FileFormat::Wasm => "application/wasm",
+            FileFormat::Elf => "application/octet-stream",
+        }
+    }
+
+    pub fn is_image(self) -> bool {
+        matches!(
+            self,
+            FileFormat::Png
+                | FileFormat::Jpeg
+                | FileFormat::Gif
+                | FileFormat::WebP
+                | FileFormat::Bmp
+        )
+    }
+
+    pub fn get_data_url(self, buffer: &[u8]) -> String {
+        format!(
+            "data:{};base64,{}",
+            self.get_mime_type(),
+            base64::encode(buffer)
+        )
+    }
+}
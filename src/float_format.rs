@@ -0,0 +1,274 @@
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FloatFormat {
+    Shortest,
+    Exact,
+    Hex,
+    Parts,
+}
+
+struct Layout {
+    exponent_bits: u32,
+    mantissa_bits: u32,
+    hex_digits: usize,
+}
+
+impl Layout {
+    fn of(width: u32) -> Self {
+        if width == 32 {
+            Layout {
+                exponent_bits: 8,
+                mantissa_bits: 23,
+                hex_digits: 6,
+            }
+        } else {
+            Layout {
+                exponent_bits: 11,
+                mantissa_bits: 52,
+                hex_digits: 13,
+            }
+        }
+    }
+
+    fn bias(&self) -> i64 {
+        (1 << (self.exponent_bits - 1)) - 1
+    }
+
+    fn max_exponent(&self) -> u64 {
+        (1 << self.exponent_bits) - 1
+    }
+}
+
+struct Parts {
+    negative: bool,
+    exponent: u64,
+    mantissa: u64,
+}
+
+impl Parts {
+    fn split(bits: u64, layout: &Layout) -> Self {
+        Parts {
+            negative: bits >> (layout.exponent_bits + layout.mantissa_bits) & 1 == 1,
+            exponent: bits >> layout.mantissa_bits & layout.max_exponent(),
+            mantissa: bits & ((1 << layout.mantissa_bits) - 1),
+        }
+    }
+
+    fn sign(&self) -> &'static str {
+        if self.negative {
+            "-"
+        } else {
+            ""
+        }
+    }
+}
+
+impl FloatFormat {
+    pub fn all() -> &'static [FloatFormat] {
+        &[
+            FloatFormat::Shortest,
+            FloatFormat::Exact,
+            FloatFormat::Hex,
+            FloatFormat::Parts,
+        ]
+    }
+
+    pub fn to_string(self) -> &'static str {
+        match self {
+            FloatFormat::Shortest => "shortest",
+            FloatFormat::Exact => "exact",
+            FloatFormat::Hex => "hex float",
+            FloatFormat::Parts => "bits",
+        }
+    }
+
+    pub fn parse(source: &str) -> Option<Self> {
+        FloatFormat::all()
+            .iter()
+            .copied()
+            .find(|format| format.to_string() == source)
+    }
+
+    // The shortest form is left to the interpretation itself.
+    pub fn format(self, bits: u64, width: u32) -> Option<String> {
+        let layout = Layout::of(width);
+        let parts = Parts::split(bits, &layout);
+
+        match self {
+            FloatFormat::Shortest => None,
+            FloatFormat::Exact => Some(format_exact(&parts, &layout)),
+            FloatFormat::Hex => Some(format_hex(&parts, &layout)),
+            FloatFormat::Parts => Some(format_parts(&parts, &layout)),
+        }
+    }
+}
+
+fn format_special(parts: &Parts, layout: &Layout) -> Option<String> {
+    if parts.exponent != layout.max_exponent() {
+        return None;
+    }
+
+    Some(if parts.mantissa == 0 {
+        format!("{}inf", parts.sign())
+    } else {
+        format!("{}NaN (payload 0x{:x})", parts.sign(), parts.mantissa)
+    })
+}
+
+// Every finite binary float is a mantissa times a power of two, which has a
+// finite decimal expansion: m * 2^-k is m * 5^k shifted k decimal places.
+fn format_exact(parts: &Parts, layout: &Layout) -> String {
+    if let Some(special) = format_special(parts, layout) {
+        return special;
+    }
+
+    let (mantissa, exponent) = if parts.exponent == 0 {
+        (
+            parts.mantissa,
+            1 - layout.bias() - layout.mantissa_bits as i64,
+        )
+    } else {
+        (
+            parts.mantissa | 1 << layout.mantissa_bits,
+            parts.exponent as i64 - layout.bias() - layout.mantissa_bits as i64,
+        )
+    };
+
+    let mut digits = Decimal::from(mantissa);
+    let fraction_digits = if exponent >= 0 {
+        for _ in 0..exponent {
+            digits.multiply(2);
+        }
+        0
+    } else {
+        for _ in 0..-exponent {
+            digits.multiply(5);
+        }
+        -exponent as usize
+    };
+
+    let mut digits = digits.to_digits();
+    if digits.len() <= fraction_digits {
+        digits = "0".repeat(fraction_digits - digits.len() + 1) + &digits;
+    }
+
+    let (integer, fraction) = digits.split_at(digits.len() - fraction_digits);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        format!("{}{}", parts.sign(), integer)
+    } else {
+        format!("{}{}.{}", parts.sign(), integer, fraction)
+    }
+}
+
+// Like the C `%a` format, subnormals keep a leading 0 and the smallest
+// normal exponent.
+fn format_hex(parts: &Parts, layout: &Layout) -> String {
+    if let Some(special) = format_special(parts, layout) {
+        return special;
+    }
+
+    if parts.exponent == 0 && parts.mantissa == 0 {
+        return format!("{}0x0p+0", parts.sign());
+    }
+
+    let (leading, exponent) = if parts.exponent == 0 {
+        (0, 1 - layout.bias())
+    } else {
+        (1, parts.exponent as i64 - layout.bias())
+    };
+
+    let hex_digits = layout.hex_digits;
+    let shift = hex_digits as u32 * 4 - layout.mantissa_bits;
+    let fraction = format!("{:0width$x}", parts.mantissa << shift, width = hex_digits);
+    let fraction = fraction.trim_end_matches('0');
+
+    format!(
+        "{}0x{}{}{}p{:+}",
+        parts.sign(),
+        leading,
+        if fraction.is_empty() { "" } else { "." },
+        fraction,
+        exponent
+    )
+}
+
+fn format_parts(parts: &Parts, layout: &Layout) -> String {
+    let class = if parts.exponent == layout.max_exponent() {
+        if parts.mantissa == 0 {
+            "infinity"
+        } else if parts.mantissa >> (layout.mantissa_bits - 1) == 1 {
+            "quiet NaN"
+        } else {
+            "signaling NaN"
+        }
+    } else if parts.exponent == 0 {
+        if parts.mantissa == 0 {
+            "zero"
+        } else {
+            "subnormal"
+        }
+    } else {
+        "normal"
+    };
+
+    format!(
+        "sign {} | exponent {:0exponent_width$b} ({:+}) | mantissa {:0mantissa_width$b} | {}",
+        if parts.negative { 1 } else { 0 },
+        parts.exponent,
+        parts.exponent as i64 - layout.bias(),
+        parts.mantissa,
+        class,
+        exponent_width = layout.exponent_bits as usize,
+        mantissa_width = layout.mantissa_bits as usize,
+    )
+}
+
+// Little-endian base 10^9 limbs, enough to multiply by small numbers.
+struct Decimal {
+    limbs: Vec<u64>,
+}
+
+const LIMB: u64 = 1_000_000_000;
+
+impl Decimal {
+    fn from(value: u64) -> Self {
+        let mut limbs = vec![];
+        let mut value = value;
+        loop {
+            limbs.push(value % LIMB);
+            value /= LIMB;
+            if value == 0 {
+                break;
+            }
+        }
+
+        Decimal { limbs }
+    }
+
+    fn multiply(&mut self, factor: u64) {
+        let mut carry = 0;
+        for limb in self.limbs.iter_mut() {
+            let product = *limb * factor + carry;
+            *limb = product % LIMB;
+            carry = product / LIMB;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    fn to_digits(&self) -> String {
+        let mut limbs = self.limbs.iter().rev();
+        let mut result = limbs
+            .next()
+            .map(|limb| limb.to_string())
+            .unwrap_or_default();
+        for limb in limbs {
+            result.push_str(&format!("{:09}", limb));
+        }
+
+        result
+    }
+}
@@ -17,6 +17,14 @@ pub fn format_as_ascii_and_hex(buffer: &[u8]) -> String {
         .fold(String::new(), |source, chars| source + &chars + "\n")
 }
 
+pub fn format_as_hex(buffer: &[u8]) -> String {
+    buffer
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[derive(Copy, Clone)]
 struct Byte(u8);
 
@@ -0,0 +1,43 @@
+pub fn encode(buffer: &[u8]) -> String {
+    encode_separated(buffer, "")
+}
+
+pub fn encode_separated(buffer: &[u8], separator: &str) -> String {
+    buffer
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+// Digits may be in any case and separated by whitespace, but every byte
+// needs both of its digits.
+pub fn decode(source: &str) -> Option<Vec<u8>> {
+    let digits = source
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .map(|ch| ch.to_digit(16))
+        .collect::<Option<Vec<_>>>()?;
+
+    if digits.len() & 1 == 1 {
+        return None;
+    }
+
+    Some(
+        digits
+            .chunks(2)
+            .map(|pair| (pair[0] << 4 | pair[1]) as u8)
+            .collect(),
+    )
+}
+
+// Reads one or two digits, as in 0x5 or \x0a.
+pub fn decode_byte(digits: &str) -> Option<u8> {
+    if digits.is_empty() || digits.len() > 2 {
+        return None;
+    }
+
+    digits
+        .chars()
+        .try_fold(0, |byte, ch| Some(byte << 4 | ch.to_digit(16)? as u8))
+}
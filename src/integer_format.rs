@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum IntegerFormat {
+    Decimal,
+    Hex,
+    Octal,
+    Binary,
+    Flags,
+}
+
+impl IntegerFormat {
+    pub fn all() -> &'static [IntegerFormat] {
+        &[
+            IntegerFormat::Decimal,
+            IntegerFormat::Hex,
+            IntegerFormat::Octal,
+            IntegerFormat::Binary,
+            IntegerFormat::Flags,
+        ]
+    }
+
+    pub fn to_string(self) -> &'static str {
+        match self {
+            IntegerFormat::Decimal => "dec",
+            IntegerFormat::Hex => "hex",
+            IntegerFormat::Octal => "oct",
+            IntegerFormat::Binary => "bin",
+            IntegerFormat::Flags => "flags",
+        }
+    }
+
+    pub fn parse(source: &str) -> Option<Self> {
+        IntegerFormat::all()
+            .iter()
+            .copied()
+            .find(|format| format.to_string() == source)
+    }
+
+    // Negative values are shown as their two's complement in the width of
+    // the interpretation. Decimal is left to the interpretation itself.
+    pub fn format(
+        self,
+        bits: u64,
+        width: u32,
+        bit_names: &BTreeMap<u32, String>,
+    ) -> Option<String> {
+        match self {
+            IntegerFormat::Decimal => None,
+            IntegerFormat::Hex => Some(format!("0x{:x}", bits)),
+            IntegerFormat::Octal => Some(format!("0o{:o}", bits)),
+            IntegerFormat::Binary => Some(format!("0b{:b}", bits)),
+            IntegerFormat::Flags => Some(format_flags(bits, width, bit_names)),
+        }
+    }
+}
+
+fn format_flags(bits: u64, width: u32, bit_names: &BTreeMap<u32, String>) -> String {
+    let flags: Vec<String> = (0..width)
+        .filter(|bit| bits >> bit & 1 == 1)
+        .map(|bit| match bit_names.get(&bit) {
+            Some(name) => name.clone(),
+            None => format!("bit {}", bit),
+        })
+        .collect();
+
+    if flags.is_empty() {
+        "(no flags)".to_string()
+    } else {
+        flags.join(" | ")
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{
+    kind32::Kind32, kind64::Kind64, kind_bytes::KindBytes, kind_message::KindMessage,
+    kind_varint::KindVarint, model::DecodingState,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Interpretation {
+    Utf8String,
+    Bytes(KindBytes),
+    Message(KindMessage),
+    Varint(KindVarint),
+    Fixed32(Kind32),
+    Fixed64(Kind64),
+}
+
+impl Interpretation {
+    pub fn from_state(state: &DecodingState) -> Option<Self> {
+        match state {
+            DecodingState::Varint { kind, .. } => Some(Interpretation::Varint(*kind)),
+            DecodingState::Fixed32 { kind, .. } => Some(Interpretation::Fixed32(*kind)),
+            DecodingState::Fixed64 { kind, .. } => Some(Interpretation::Fixed64(*kind)),
+            DecodingState::Utf8String { .. } => Some(Interpretation::Utf8String),
+            DecodingState::Bytes { kind, .. } => Some(Interpretation::Bytes(*kind)),
+            DecodingState::Message { kind, .. } => Some(Interpretation::Message(*kind)),
+            DecodingState::Chunk { .. } => None,
+        }
+    }
+
+    pub fn get_description(self) -> String {
+        match self {
+            Interpretation::Utf8String => "utf-8".to_string(),
+            Interpretation::Bytes(kind) => format!("bytes {}", kind.to_string()),
+            Interpretation::Message(kind) => kind.to_string().to_string(),
+            Interpretation::Varint(kind) => format!("varint {}", kind.to_string()),
+            Interpretation::Fixed32(kind) => format!("fix 32 {}", kind.to_string()),
+            Interpretation::Fixed64(kind) => format!("fix 64 {}", kind.to_string()),
+        }
+    }
+
+    // Nodes of another wire type at the same path are left as they are.
+    pub fn apply(self, state: &mut DecodingState) {
+        let uuid = state.get_uuid();
+
+        match (self, state) {
+            (Interpretation::Varint(varint_kind), DecodingState::Varint { kind, .. }) => {
+                *kind = varint_kind
+            }
+            (Interpretation::Fixed32(kind32), DecodingState::Fixed32 { kind, .. }) => {
+                *kind = kind32
+            }
+            (Interpretation::Fixed64(kind64), DecodingState::Fixed64 { kind, .. }) => {
+                *kind = kind64
+            }
+            (Interpretation::Utf8String, state) => state.decode_as_utf8_string(uuid),
+            (Interpretation::Bytes(kind), state) => state.decode_as_bytes(uuid, kind),
+            (Interpretation::Message(message_kind), state) => {
+                state.decode_as_message(uuid);
+                state.select_message_kind(uuid, message_kind);
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,5 +1,7 @@
-use byteorder::{LittleEndian, ReadBytesExt};
+use crate::timestamp_unit::TimestampUnit;
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
 use std::io::Cursor;
+use std::net::Ipv4Addr;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Kind32 {
@@ -7,15 +9,42 @@ pub enum Kind32 {
     Fixed32,
     SFixed32,
     Float,
+    Timestamp(TimestampUnit),
+    Fixed32BigEndian,
+    SFixed32BigEndian,
+    FloatBigEndian,
+    Ipv4,
 }
 
 impl Kind32 {
+    pub fn all() -> &'static [Kind32] {
+        &[
+            Kind32::Buffer,
+            Kind32::Fixed32,
+            Kind32::SFixed32,
+            Kind32::Float,
+            Kind32::Timestamp(TimestampUnit::Seconds),
+            Kind32::Timestamp(TimestampUnit::Milliseconds),
+            Kind32::Timestamp(TimestampUnit::Microseconds),
+            Kind32::Timestamp(TimestampUnit::Nanoseconds),
+            Kind32::Fixed32BigEndian,
+            Kind32::SFixed32BigEndian,
+            Kind32::FloatBigEndian,
+            Kind32::Ipv4,
+        ]
+    }
+
     pub fn toggle(self) -> Self {
         match self {
             Kind32::Buffer => Kind32::Fixed32,
             Kind32::Fixed32 => Kind32::SFixed32,
             Kind32::SFixed32 => Kind32::Float,
-            Kind32::Float => Kind32::Buffer,
+            Kind32::Float
+            | Kind32::Timestamp(_)
+            | Kind32::Fixed32BigEndian
+            | Kind32::SFixed32BigEndian
+            | Kind32::FloatBigEndian
+            | Kind32::Ipv4 => Kind32::Buffer,
         }
     }
 
@@ -25,23 +54,94 @@ impl Kind32 {
             Kind32::Fixed32 => "unsign",
             Kind32::SFixed32 => "sign",
             Kind32::Float => "float",
+            Kind32::Timestamp(TimestampUnit::Seconds) => "timestamp s",
+            Kind32::Timestamp(TimestampUnit::Milliseconds) => "timestamp ms",
+            Kind32::Timestamp(TimestampUnit::Microseconds) => "timestamp µs",
+            Kind32::Timestamp(TimestampUnit::Nanoseconds) => "timestamp ns",
+            Kind32::Fixed32BigEndian => "unsign BE",
+            Kind32::SFixed32BigEndian => "sign BE",
+            Kind32::FloatBigEndian => "float BE",
+            Kind32::Ipv4 => "ipv4",
         }
     }
+
+    pub fn parse(source: &str) -> Option<Self> {
+        Kind32::all()
+            .iter()
+            .copied()
+            .find(|kind| kind.to_string() == source)
+    }
     pub fn get_value_as_string(self, buffer: &[u8]) -> String {
         match self {
             Kind32::Buffer => crate::format::format_as_ascii_and_hex(buffer),
             Kind32::Fixed32 => {
                 let mut cursor = Cursor::new(buffer);
-                cursor.read_i32::<LittleEndian>().unwrap().to_string()
+                cursor.read_u32::<LittleEndian>().unwrap().to_string()
             }
             Kind32::SFixed32 => {
                 let mut cursor = Cursor::new(buffer);
-                cursor.read_u32::<LittleEndian>().unwrap().to_string()
+                cursor.read_i32::<LittleEndian>().unwrap().to_string()
             }
             Kind32::Float => {
                 let mut cursor = Cursor::new(buffer);
                 cursor.read_f32::<LittleEndian>().unwrap().to_string()
             }
+            Kind32::Timestamp(unit) => unit.get_value_as_string(read_timestamp_value(buffer)),
+            Kind32::Fixed32BigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                cursor.read_u32::<BigEndian>().unwrap().to_string()
+            }
+            Kind32::SFixed32BigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                cursor.read_i32::<BigEndian>().unwrap().to_string()
+            }
+            Kind32::FloatBigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                cursor.read_f32::<BigEndian>().unwrap().to_string()
+            }
+            // Addresses are usually stored as a host order integer, so the
+            // number is read as fixed32 and printed most significant byte first.
+            Kind32::Ipv4 => {
+                let mut cursor = Cursor::new(buffer);
+                Ipv4Addr::from(cursor.read_u32::<LittleEndian>().unwrap()).to_string()
+            }
         }
     }
+
+    pub fn get_integer_bits(self, buffer: &[u8]) -> Option<(u64, u32)> {
+        match self {
+            Kind32::Fixed32 | Kind32::SFixed32 => {
+                let mut cursor = Cursor::new(buffer);
+                Some((cursor.read_u32::<LittleEndian>().unwrap() as u64, 32))
+            }
+            Kind32::Fixed32BigEndian | Kind32::SFixed32BigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                Some((cursor.read_u32::<BigEndian>().unwrap() as u64, 32))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn get_float_bits(self, buffer: &[u8]) -> Option<(u64, u32)> {
+        match self {
+            Kind32::Float => {
+                let mut cursor = Cursor::new(buffer);
+                Some((cursor.read_u32::<LittleEndian>().unwrap() as u64, 32))
+            }
+            Kind32::FloatBigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                Some((cursor.read_u32::<BigEndian>().unwrap() as u64, 32))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn suggest_timestamp_unit(buffer: &[u8]) -> Option<TimestampUnit> {
+        TimestampUnit::suggest(read_timestamp_value(buffer))
+    }
+}
+
+fn read_timestamp_value(buffer: &[u8]) -> i128 {
+    let mut cursor = Cursor::new(buffer);
+    cursor.read_u32::<LittleEndian>().unwrap() as i128
 }
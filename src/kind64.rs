@@ -1,4 +1,5 @@
-use byteorder::{LittleEndian, ReadBytesExt};
+use crate::timestamp_unit::TimestampUnit;
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
 use std::io::Cursor;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -7,15 +8,39 @@ pub enum Kind64 {
     Fixed64,
     SFixed64,
     Double,
+    Timestamp(TimestampUnit),
+    Fixed64BigEndian,
+    SFixed64BigEndian,
+    DoubleBigEndian,
 }
 
 impl Kind64 {
+    pub fn all() -> &'static [Kind64] {
+        &[
+            Kind64::Buffer,
+            Kind64::Fixed64,
+            Kind64::SFixed64,
+            Kind64::Double,
+            Kind64::Timestamp(TimestampUnit::Seconds),
+            Kind64::Timestamp(TimestampUnit::Milliseconds),
+            Kind64::Timestamp(TimestampUnit::Microseconds),
+            Kind64::Timestamp(TimestampUnit::Nanoseconds),
+            Kind64::Fixed64BigEndian,
+            Kind64::SFixed64BigEndian,
+            Kind64::DoubleBigEndian,
+        ]
+    }
+
     pub fn toggle(self) -> Self {
         match self {
             Kind64::Buffer => Kind64::Fixed64,
             Kind64::Fixed64 => Kind64::SFixed64,
             Kind64::SFixed64 => Kind64::Double,
-            Kind64::Double => Kind64::Buffer,
+            Kind64::Double
+            | Kind64::Timestamp(_)
+            | Kind64::Fixed64BigEndian
+            | Kind64::SFixed64BigEndian
+            | Kind64::DoubleBigEndian => Kind64::Buffer,
         }
     }
 
@@ -25,24 +50,88 @@ impl Kind64 {
             Kind64::Fixed64 => "unsign",
             Kind64::SFixed64 => "sign",
             Kind64::Double => "double",
+            Kind64::Timestamp(TimestampUnit::Seconds) => "timestamp s",
+            Kind64::Timestamp(TimestampUnit::Milliseconds) => "timestamp ms",
+            Kind64::Timestamp(TimestampUnit::Microseconds) => "timestamp µs",
+            Kind64::Timestamp(TimestampUnit::Nanoseconds) => "timestamp ns",
+            Kind64::Fixed64BigEndian => "unsign BE",
+            Kind64::SFixed64BigEndian => "sign BE",
+            Kind64::DoubleBigEndian => "double BE",
         }
     }
 
+    pub fn parse(source: &str) -> Option<Self> {
+        Kind64::all()
+            .iter()
+            .copied()
+            .find(|kind| kind.to_string() == source)
+    }
+
     pub fn get_value_as_string(self, buffer: &[u8]) -> String {
         match self {
             Kind64::Buffer => crate::format::format_as_ascii_and_hex(buffer),
             Kind64::Fixed64 => {
                 let mut cursor = Cursor::new(buffer);
-                cursor.read_i64::<LittleEndian>().unwrap().to_string()
+                cursor.read_u64::<LittleEndian>().unwrap().to_string()
             }
             Kind64::SFixed64 => {
                 let mut cursor = Cursor::new(buffer);
-                cursor.read_u64::<LittleEndian>().unwrap().to_string()
+                cursor.read_i64::<LittleEndian>().unwrap().to_string()
             }
             Kind64::Double => {
                 let mut cursor = Cursor::new(buffer);
                 cursor.read_f64::<LittleEndian>().unwrap().to_string()
             }
+            Kind64::Timestamp(unit) => unit.get_value_as_string(read_timestamp_value(buffer)),
+            Kind64::Fixed64BigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                cursor.read_u64::<BigEndian>().unwrap().to_string()
+            }
+            Kind64::SFixed64BigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                cursor.read_i64::<BigEndian>().unwrap().to_string()
+            }
+            Kind64::DoubleBigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                cursor.read_f64::<BigEndian>().unwrap().to_string()
+            }
         }
     }
+
+    pub fn get_integer_bits(self, buffer: &[u8]) -> Option<(u64, u32)> {
+        match self {
+            Kind64::Fixed64 | Kind64::SFixed64 => {
+                let mut cursor = Cursor::new(buffer);
+                Some((cursor.read_u64::<LittleEndian>().unwrap(), 64))
+            }
+            Kind64::Fixed64BigEndian | Kind64::SFixed64BigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                Some((cursor.read_u64::<BigEndian>().unwrap(), 64))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn get_float_bits(self, buffer: &[u8]) -> Option<(u64, u32)> {
+        match self {
+            Kind64::Double => {
+                let mut cursor = Cursor::new(buffer);
+                Some((cursor.read_u64::<LittleEndian>().unwrap(), 64))
+            }
+            Kind64::DoubleBigEndian => {
+                let mut cursor = Cursor::new(buffer);
+                Some((cursor.read_u64::<BigEndian>().unwrap(), 64))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn suggest_timestamp_unit(buffer: &[u8]) -> Option<TimestampUnit> {
+        TimestampUnit::suggest(read_timestamp_value(buffer))
+    }
+}
+
+fn read_timestamp_value(buffer: &[u8]) -> i128 {
+    let mut cursor = Cursor::new(buffer);
+    cursor.read_i64::<LittleEndian>().unwrap() as i128
 }
@@ -0,0 +1,145 @@
+use crate::{
+    base64, cbor, document::DocumentValue, error::ProtodecError, format, hex, message_pack,
+    text_encoding,
+};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KindBytes {
+    Hex,
+    Base64,
+    Uuid,
+    IpAddress,
+    MacAddress,
+    Utf8Lossy,
+    Latin1,
+    Utf16Le,
+    Utf16Be,
+    Ascii,
+    Cbor,
+    MessagePack,
+}
+
+impl KindBytes {
+    pub fn all() -> &'static [KindBytes] {
+        &[
+            KindBytes::Hex,
+            KindBytes::Base64,
+            KindBytes::Uuid,
+            KindBytes::IpAddress,
+            KindBytes::MacAddress,
+            KindBytes::Utf8Lossy,
+            KindBytes::Latin1,
+            KindBytes::Utf16Le,
+            KindBytes::Utf16Be,
+            KindBytes::Ascii,
+            KindBytes::Cbor,
+            KindBytes::MessagePack,
+        ]
+    }
+
+    pub fn to_string(self) -> &'static str {
+        match self {
+            KindBytes::Hex => "hex",
+            KindBytes::Base64 => "base64",
+            KindBytes::Uuid => "uuid",
+            KindBytes::IpAddress => "ip",
+            KindBytes::MacAddress => "mac",
+            KindBytes::Utf8Lossy => "utf-8 lossy",
+            KindBytes::Latin1 => "latin-1",
+            KindBytes::Utf16Le => "utf-16le",
+            KindBytes::Utf16Be => "utf-16be",
+            KindBytes::Ascii => "ascii",
+            KindBytes::Cbor => "cbor",
+            KindBytes::MessagePack => "msgpack",
+        }
+    }
+
+    pub fn parse(source: &str) -> Option<Self> {
+        KindBytes::all()
+            .iter()
+            .copied()
+            .find(|kind| kind.to_string() == source)
+    }
+
+    pub fn fits(self, buffer: &[u8]) -> bool {
+        match self {
+            KindBytes::Hex
+            | KindBytes::Base64
+            | KindBytes::Utf8Lossy
+            | KindBytes::Latin1
+            | KindBytes::Ascii
+            | KindBytes::Cbor
+            | KindBytes::MessagePack => true,
+            KindBytes::Utf16Le | KindBytes::Utf16Be => buffer.len() & 1 == 0,
+            KindBytes::Uuid => buffer.len() == 16,
+            KindBytes::IpAddress => buffer.len() == 4 || buffer.len() == 16,
+            KindBytes::MacAddress => buffer.len() == 6 || buffer.len() == 8,
+        }
+    }
+
+    pub fn decode_document(self, buffer: &[u8]) -> Option<Result<DocumentValue, ProtodecError>> {
+        match self {
+            KindBytes::Cbor => Some(cbor::decode(buffer)),
+            KindBytes::MessagePack => Some(message_pack::decode(buffer)),
+            _ => None,
+        }
+    }
+
+    pub fn get_value_as_string(self, buffer: &[u8]) -> String {
+        if !self.fits(buffer) {
+            return format!(
+                "{} (not a {} value)",
+                format::format_as_hex(buffer),
+                self.to_string()
+            );
+        }
+
+        match self {
+            KindBytes::Hex => hex::encode(buffer),
+            KindBytes::Base64 => base64::encode(buffer),
+            KindBytes::Uuid => format_uuid(buffer),
+            KindBytes::IpAddress => format_ip_address(buffer),
+            KindBytes::MacAddress => hex::encode_separated(buffer, ":"),
+            KindBytes::Utf8Lossy => {
+                text_encoding::format_segments(&text_encoding::decode_utf8_lossy(buffer))
+            }
+            KindBytes::Latin1 => text_encoding::decode_latin1(buffer),
+            KindBytes::Utf16Le => text_encoding::decode_utf16(buffer, false),
+            KindBytes::Utf16Be => text_encoding::decode_utf16(buffer, true),
+            KindBytes::Ascii => text_encoding::escape_ascii(buffer),
+            KindBytes::Cbor | KindBytes::MessagePack => match self.decode_document(buffer) {
+                Some(Ok(value)) => value.format(),
+                Some(Err(err)) => format!(
+                    "{} (invalid {})",
+                    format::format_as_hex(buffer),
+                    err.message
+                ),
+                None => format::format_as_hex(buffer),
+            },
+        }
+    }
+}
+
+fn format_uuid(buffer: &[u8]) -> String {
+    let digits = hex::encode(buffer);
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &digits[0..8],
+        &digits[8..12],
+        &digits[12..16],
+        &digits[16..20],
+        &digits[20..32]
+    )
+}
+
+fn format_ip_address(buffer: &[u8]) -> String {
+    if buffer.len() == 4 {
+        Ipv4Addr::new(buffer[0], buffer[1], buffer[2], buffer[3]).to_string()
+    } else {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(buffer);
+        Ipv6Addr::from(octets).to_string()
+    }
+}
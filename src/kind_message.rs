@@ -0,0 +1,445 @@
+use crate::{entropy, format, model::DecodingState, wire_type::WireType};
+use byteorder::{LittleEndian, ReadBytesExt};
+use serde_json::{Map, Number, Value};
+use std::io::Cursor;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+pub const MIN_TIMESTAMP_SECONDS: i64 = -62_135_596_800;
+pub const MAX_TIMESTAMP_SECONDS: i64 = 253_402_300_799;
+const MAX_DURATION_SECONDS: i64 = 315_576_000_000;
+
+// Only seconds between 1990 and 2100 make a message look like a timestamp,
+// small numbers are much more often counters or identifiers.
+pub const SUGGESTED_TIMESTAMP_SECONDS: std::ops::Range<i64> = 631_152_000..4_102_444_800;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KindMessage {
+    Message,
+    Timestamp,
+    Duration,
+    DoubleValue,
+    FloatValue,
+    Int64Value,
+    UInt64Value,
+    Int32Value,
+    UInt32Value,
+    BoolValue,
+    StringValue,
+    BytesValue,
+    Struct,
+    Value,
+    ListValue,
+    Any,
+}
+
+impl KindMessage {
+    pub fn all() -> &'static [KindMessage] {
+        &[
+            KindMessage::Message,
+            KindMessage::Timestamp,
+            KindMessage::Duration,
+            KindMessage::DoubleValue,
+            KindMessage::FloatValue,
+            KindMessage::Int64Value,
+            KindMessage::UInt64Value,
+            KindMessage::Int32Value,
+            KindMessage::UInt32Value,
+            KindMessage::BoolValue,
+            KindMessage::StringValue,
+            KindMessage::BytesValue,
+            KindMessage::Struct,
+            KindMessage::Value,
+            KindMessage::ListValue,
+            KindMessage::Any,
+        ]
+    }
+
+    pub fn to_string(self) -> &'static str {
+        match self {
+            KindMessage::Message => "message",
+            KindMessage::Timestamp => "Timestamp",
+            KindMessage::Duration => "Duration",
+            KindMessage::DoubleValue => "DoubleValue",
+            KindMessage::FloatValue => "FloatValue",
+            KindMessage::Int64Value => "Int64Value",
+            KindMessage::UInt64Value => "UInt64Value",
+            KindMessage::Int32Value => "Int32Value",
+            KindMessage::UInt32Value => "UInt32Value",
+            KindMessage::BoolValue => "BoolValue",
+            KindMessage::StringValue => "StringValue",
+            KindMessage::BytesValue => "BytesValue",
+            KindMessage::Struct => "Struct",
+            KindMessage::Value => "Value",
+            KindMessage::ListValue => "ListValue",
+            KindMessage::Any => "Any",
+        }
+    }
+
+    pub fn parse(source: &str) -> Option<Self> {
+        KindMessage::all()
+            .iter()
+            .copied()
+            .find(|kind| kind.to_string() == source)
+    }
+
+    pub fn from_type_name(type_name: &str) -> Option<Self> {
+        let type_name = type_name.trim_start_matches('.');
+
+        if !type_name.starts_with("google.protobuf.") {
+            return None;
+        }
+
+        KindMessage::parse(&type_name["google.protobuf.".len()..])
+            .filter(|kind| *kind != KindMessage::Message)
+    }
+
+    pub fn suggest(items: &[DecodingState]) -> Option<Self> {
+        if items.is_empty() {
+            return None;
+        }
+
+        if get_any_type_url(items).is_some() {
+            return Some(KindMessage::Any);
+        }
+
+        if let (Some(seconds), Some(_)) = (
+            get_varint(items, 1).map(|value| value as u64 as i64),
+            KindMessage::Timestamp.get_value_as_string(items),
+        ) {
+            if SUGGESTED_TIMESTAMP_SECONDS.contains(&seconds) {
+                return Some(KindMessage::Timestamp);
+            }
+        }
+
+        if items.len() == 2
+            && items.iter().any(|item| item.get_field_number() == 2)
+            && KindMessage::Duration.get_value_as_string(items).is_some()
+        {
+            return Some(KindMessage::Duration);
+        }
+
+        if KindMessage::Struct.get_value_as_string(items).is_some() {
+            return Some(KindMessage::Struct);
+        }
+
+        match items {
+            [item] if item.get_field_number() == 1 => match item {
+                DecodingState::Varint { .. } => Some(KindMessage::Int64Value),
+                DecodingState::Fixed64 { .. } => Some(KindMessage::DoubleValue),
+                DecodingState::Fixed32 { .. } => Some(KindMessage::FloatValue),
+                DecodingState::Utf8String { .. } => Some(KindMessage::StringValue),
+                DecodingState::Chunk { buffer, .. } if entropy::is_high_entropy(buffer) => {
+                    Some(KindMessage::BytesValue)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // Returns None when the fields don't fit the well-known type, so the
+    // message is shown field by field instead.
+    pub fn get_value_as_string(self, items: &[DecodingState]) -> Option<String> {
+        match self {
+            KindMessage::Message | KindMessage::Any => None,
+            KindMessage::Timestamp => {
+                let (seconds, nanos) = get_seconds_and_nanos(items)?;
+
+                if !(MIN_TIMESTAMP_SECONDS..=MAX_TIMESTAMP_SECONDS).contains(&seconds) || nanos < 0
+                {
+                    return None;
+                }
+
+                Some(format_timestamp(seconds, nanos))
+            }
+            KindMessage::Duration => {
+                let (seconds, nanos) = get_seconds_and_nanos(items)?;
+
+                if seconds.abs() > MAX_DURATION_SECONDS
+                    || (seconds > 0 && nanos < 0)
+                    || (seconds < 0 && nanos > 0)
+                {
+                    return None;
+                }
+
+                Some(format_duration(seconds, nanos))
+            }
+            KindMessage::Struct | KindMessage::Value | KindMessage::ListValue => {
+                let value = match self {
+                    KindMessage::Struct => get_struct(items)?,
+                    KindMessage::Value => get_value(items)?,
+                    _ => get_list_value(items)?,
+                };
+
+                serde_json::to_string_pretty(&value).ok()
+            }
+            _ => {
+                if items.iter().any(|item| item.get_field_number() != 1) {
+                    return None;
+                }
+
+                get_wrapped_value(self, items)
+            }
+        }
+    }
+}
+
+// The type url of an Any is usually `type.googleapis.com/package.Message`,
+// but any prefix ending with a slash is allowed.
+pub fn get_any_type_url(items: &[DecodingState]) -> Option<String> {
+    if items.iter().any(|item| {
+        let field_number = item.get_field_number();
+        (field_number != 1 && field_number != 2)
+            || item.get_wire_type() != WireType::LengthDelimited
+    }) {
+        return None;
+    }
+
+    let type_url = find_item(items, 1).and_then(|_| get_string(items, 1))?;
+    let type_name = get_any_type_name(&type_url);
+
+    let is_type_name = !type_name.is_empty()
+        && type_url.len() > type_name.len()
+        && type_name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '.');
+
+    if is_type_name {
+        Some(type_url)
+    } else {
+        None
+    }
+}
+
+pub fn get_any_type_name(type_url: &str) -> &str {
+    match type_url.rfind('/') {
+        Some(index) => &type_url[index + 1..],
+        None => type_url,
+    }
+}
+
+fn get_wrapped_value(kind: KindMessage, items: &[DecodingState]) -> Option<String> {
+    let value = match kind {
+        KindMessage::DoubleValue => get_fixed64(items, 1)?.to_string(),
+        KindMessage::FloatValue => get_fixed32(items, 1)?.to_string(),
+        KindMessage::Int64Value => (get_varint(items, 1)? as u64 as i64).to_string(),
+        KindMessage::UInt64Value => (get_varint(items, 1)? as u64).to_string(),
+        KindMessage::Int32Value => (get_varint(items, 1)? as u64 as i32).to_string(),
+        KindMessage::UInt32Value => (get_varint(items, 1)? as u32).to_string(),
+        KindMessage::BoolValue => (get_varint(items, 1)? != 0).to_string(),
+        KindMessage::StringValue => Value::String(get_string(items, 1)?).to_string(),
+        KindMessage::BytesValue => format::format_as_hex(&get_bytes(items, 1)?),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+fn get_seconds_and_nanos(items: &[DecodingState]) -> Option<(i64, i64)> {
+    if items
+        .iter()
+        .any(|item| item.get_field_number() != 1 && item.get_field_number() != 2)
+    {
+        return None;
+    }
+
+    let seconds = get_varint(items, 1)? as u64 as i64;
+    // Nanos are int32, so negative values arrive sign extended to 64 bits
+    let nanos = get_varint(items, 2)? as u64 as i64;
+
+    if nanos <= -NANOS_PER_SECOND || nanos >= NANOS_PER_SECOND {
+        return None;
+    }
+
+    Some((seconds, nanos))
+}
+
+pub fn format_timestamp(seconds: i64, nanos: i64) -> String {
+    let days = seconds.div_euclid(86_400);
+    let seconds_of_day = seconds.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+        format_nanos(nanos)
+    )
+}
+
+pub fn format_duration(seconds: i64, nanos: i64) -> String {
+    let sign = if seconds < 0 || nanos < 0 { "-" } else { "" };
+    let seconds = seconds.abs();
+
+    let units = [
+        (seconds / 86_400, "d"),
+        (seconds % 86_400 / 3600, "h"),
+        (seconds % 3600 / 60, "m"),
+    ];
+
+    let mut parts: Vec<String> = units
+        .iter()
+        .skip_while(|(value, _)| *value == 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+    parts.push(format!("{}{}s", seconds % 60, format_nanos(nanos.abs())));
+
+    format!("{}{}", sign, parts.join(" "))
+}
+
+// Like the protobuf JSON mapping, fractions use 0, 3, 6 or 9 digits.
+fn format_nanos(nanos: i64) -> String {
+    if nanos == 0 {
+        String::new()
+    } else if nanos % 1_000_000 == 0 {
+        format!(".{:03}", nanos / 1_000_000)
+    } else if nanos % 1000 == 0 {
+        format!(".{:06}", nanos / 1000)
+    } else {
+        format!(".{:09}", nanos)
+    }
+}
+
+// Converts days since 1970-01-01 to a proleptic Gregorian date, see
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+fn get_struct(items: &[DecodingState]) -> Option<Value> {
+    let mut object = Map::new();
+
+    for item in items {
+        if item.get_field_number() != 1 {
+            return None;
+        }
+
+        let entry = item.get_message_items()?;
+        if entry
+            .iter()
+            .any(|field| field.get_field_number() != 1 && field.get_field_number() != 2)
+        {
+            return None;
+        }
+
+        let key = get_string(&entry, 1)?;
+        let value = match find_item(&entry, 2) {
+            Some(value) => get_value(&value.get_message_items()?)?,
+            None => Value::Null,
+        };
+
+        object.insert(key, value);
+    }
+
+    Some(Value::Object(object))
+}
+
+fn get_list_value(items: &[DecodingState]) -> Option<Value> {
+    items
+        .iter()
+        .map(|item| {
+            if item.get_field_number() == 1 {
+                get_value(&item.get_message_items()?)
+            } else {
+                None
+            }
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(Value::Array)
+}
+
+fn get_value(items: &[DecodingState]) -> Option<Value> {
+    let item = match items.last() {
+        Some(item) => item,
+        None => return Some(Value::Null),
+    };
+
+    let value = match item.get_field_number() {
+        1 => Value::Null,
+        2 => {
+            let number = get_fixed64(items, 2)?;
+            match Number::from_f64(number) {
+                Some(number) => Value::Number(number),
+                None => Value::String(number.to_string()),
+            }
+        }
+        3 => Value::String(get_string(items, 3)?),
+        4 => Value::Bool(get_varint(items, 4)? != 0),
+        5 => get_struct(&item.get_message_items()?)?,
+        6 => get_list_value(&item.get_message_items()?)?,
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+// When a field occurs several times the last occurrence wins, as it does
+// for protobuf parsers.
+fn find_item(items: &[DecodingState], field_number: u128) -> Option<&DecodingState> {
+    items
+        .iter()
+        .rev()
+        .find(|item| item.get_field_number() == field_number)
+}
+
+fn get_varint(items: &[DecodingState], field_number: u128) -> Option<u128> {
+    match find_item(items, field_number) {
+        Some(DecodingState::Varint { value, .. }) => Some(*value),
+        Some(_) => None,
+        None => Some(0),
+    }
+}
+
+fn get_fixed64(items: &[DecodingState], field_number: u128) -> Option<f64> {
+    match find_item(items, field_number) {
+        Some(DecodingState::Fixed64 { buffer, .. }) => {
+            Cursor::new(buffer).read_f64::<LittleEndian>().ok()
+        }
+        Some(_) => None,
+        None => Some(0.0),
+    }
+}
+
+fn get_fixed32(items: &[DecodingState], field_number: u128) -> Option<f32> {
+    match find_item(items, field_number) {
+        Some(DecodingState::Fixed32 { buffer, .. }) => {
+            Cursor::new(buffer).read_f32::<LittleEndian>().ok()
+        }
+        Some(_) => None,
+        None => Some(0.0),
+    }
+}
+
+fn get_bytes(items: &[DecodingState], field_number: u128) -> Option<Vec<u8>> {
+    match find_item(items, field_number) {
+        Some(DecodingState::Chunk { buffer, .. }) | Some(DecodingState::Bytes { buffer, .. }) => {
+            Some(buffer.clone())
+        }
+        Some(DecodingState::Utf8String { value, .. }) => Some(value.as_bytes().to_vec()),
+        Some(_) => None,
+        None => Some(vec![]),
+    }
+}
+
+fn get_string(items: &[DecodingState], field_number: u128) -> Option<String> {
+    String::from_utf8(get_bytes(items, field_number)?).ok()
+}
@@ -1,14 +1,41 @@
+use crate::timestamp_unit::TimestampUnit;
+use std::collections::BTreeMap;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum KindVarint {
     ZigZag,
     Unsigned,
+    Enum,
+    Int32,
+    Int64,
+    Uint32,
+    Bool,
+    Timestamp(TimestampUnit),
 }
 
 impl KindVarint {
+    pub fn all() -> &'static [KindVarint] {
+        &[
+            KindVarint::Unsigned,
+            KindVarint::ZigZag,
+            KindVarint::Int32,
+            KindVarint::Int64,
+            KindVarint::Uint32,
+            KindVarint::Bool,
+            KindVarint::Enum,
+            KindVarint::Timestamp(TimestampUnit::Seconds),
+            KindVarint::Timestamp(TimestampUnit::Milliseconds),
+            KindVarint::Timestamp(TimestampUnit::Microseconds),
+            KindVarint::Timestamp(TimestampUnit::Nanoseconds),
+        ]
+    }
+
+    // The other interpretations are chosen from a select, so toggling
+    // only switches between the two original ones.
     pub fn toggle(self) -> Self {
         match self {
             KindVarint::Unsigned => KindVarint::ZigZag,
-            KindVarint::ZigZag => KindVarint::Unsigned,
+            _ => KindVarint::Unsigned,
         }
     }
 
@@ -16,6 +43,42 @@ impl KindVarint {
         match self {
             KindVarint::ZigZag => "zigzag",
             KindVarint::Unsigned => "unsign",
+            KindVarint::Enum => "enum",
+            KindVarint::Int32 => "int32",
+            KindVarint::Int64 => "int64",
+            KindVarint::Uint32 => "uint32",
+            KindVarint::Bool => "bool",
+            KindVarint::Timestamp(TimestampUnit::Seconds) => "timestamp s",
+            KindVarint::Timestamp(TimestampUnit::Milliseconds) => "timestamp ms",
+            KindVarint::Timestamp(TimestampUnit::Microseconds) => "timestamp µs",
+            KindVarint::Timestamp(TimestampUnit::Nanoseconds) => "timestamp ns",
+        }
+    }
+
+    pub fn parse(source: &str) -> Option<Self> {
+        KindVarint::all()
+            .iter()
+            .copied()
+            .find(|kind| kind.to_string() == source)
+    }
+
+    // Negative int32 and enum values are sign extended to ten bytes on the
+    // wire, so they are checked after the conversion to i64.
+    pub fn is_in_range(self, varint: u128) -> bool {
+        if varint > u64::MAX as u128 {
+            return false;
+        }
+
+        match self {
+            KindVarint::Int32 | KindVarint::Enum => {
+                (i32::MIN as i64..=i32::MAX as i64).contains(&(varint as u64 as i64))
+            }
+            KindVarint::Uint32 => varint <= u32::MAX as u128,
+            KindVarint::Bool => varint <= 1,
+            KindVarint::ZigZag
+            | KindVarint::Unsigned
+            | KindVarint::Int64
+            | KindVarint::Timestamp(_) => true,
         }
     }
 
@@ -26,7 +89,46 @@ impl KindVarint {
                 // TODO: It looks like the logic is similar, need to join it
                 ((varint >> 1) ^ -(varint & 0x1)).to_string()
             }
-            KindVarint::Unsigned => varint.to_string(),
+            KindVarint::Unsigned | KindVarint::Uint32 => varint.to_string(),
+            // Negative values are sent as ten byte varints
+            KindVarint::Enum | KindVarint::Int32 | KindVarint::Int64 => {
+                (varint as u64 as i64).to_string()
+            }
+            KindVarint::Bool => match varint {
+                0 => "false".to_string(),
+                1 => "true".to_string(),
+                _ => varint.to_string(),
+            },
+            KindVarint::Timestamp(unit) => unit.get_value_as_string(varint as u64 as i64 as i128),
+        }
+    }
+
+    pub fn get_integer_bits(self, varint: u128) -> Option<(u64, u32)> {
+        match self {
+            KindVarint::Unsigned | KindVarint::Int64 => Some((varint as u64, 64)),
+            KindVarint::Int32 | KindVarint::Uint32 => Some((varint as u32 as u64, 32)),
+            KindVarint::ZigZag => {
+                let varint = varint as u64;
+                Some(((varint >> 1) ^ (varint & 1).wrapping_neg(), 64))
+            }
+            KindVarint::Enum | KindVarint::Bool | KindVarint::Timestamp(_) => None,
+        }
+    }
+
+    pub fn suggest_timestamp_unit(varint: u128) -> Option<TimestampUnit> {
+        if varint > u64::MAX as u128 {
+            return None;
+        }
+
+        TimestampUnit::suggest(varint as u64 as i64 as i128)
+    }
+
+    pub fn get_value_as_enum_label(varint: u128, labels: &BTreeMap<i64, String>) -> String {
+        let value = varint as u64 as i64;
+
+        match labels.get(&value) {
+            Some(label) => format!("{} ({})", label, value),
+            None => format!("{} (unknown enum value)", value),
         }
     }
 }
@@ -0,0 +1,128 @@
+use crate::{model::DecodingState, proto_type::ProtoType, wire_type::WireType};
+
+const MIN_MAP_ENTRIES: usize = 2;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MapView {
+    pub key_type: ProtoType,
+    pub value_type: ProtoType,
+}
+
+impl MapView {
+    pub fn guess(entries: &[DecodingState]) -> Self {
+        let items: Vec<DecodingState> = entries
+            .iter()
+            .filter_map(|entry| entry.get_message_items())
+            .flatten()
+            .collect();
+
+        let key_type = find_field(&items, 1)
+            .map(|key| match key {
+                DecodingState::Varint { .. } => ProtoType::Int64,
+                DecodingState::Fixed32 { .. } => ProtoType::Fixed32,
+                DecodingState::Fixed64 { .. } => ProtoType::Fixed64,
+                _ => ProtoType::String,
+            })
+            .unwrap_or(ProtoType::String);
+
+        let values: Vec<&DecodingState> = items
+            .iter()
+            .filter(|item| item.get_field_number() == 2)
+            .collect();
+
+        let value_type = match values.first() {
+            Some(DecodingState::Varint { .. }) => ProtoType::Int64,
+            Some(DecodingState::Fixed32 { .. }) => ProtoType::Fixed32,
+            Some(DecodingState::Fixed64 { .. }) => ProtoType::Fixed64,
+            Some(_) if values.iter().all(|value| is_printable_text(value)) => ProtoType::String,
+            Some(_)
+                if values
+                    .iter()
+                    .all(|value| value.get_message_items().is_some()) =>
+            {
+                ProtoType::Message
+            }
+            Some(_) => ProtoType::Bytes,
+            None => ProtoType::String,
+        };
+
+        MapView {
+            key_type,
+            value_type,
+        }
+    }
+}
+
+fn is_printable_text(state: &DecodingState) -> bool {
+    match state {
+        DecodingState::Utf8String { .. } => true,
+        DecodingState::Chunk { buffer, .. } => match std::str::from_utf8(buffer) {
+            Ok(text) => !text
+                .chars()
+                .any(|ch| ch.is_control() && !ch.is_whitespace()),
+            Err(_) => false,
+        },
+        _ => false,
+    }
+}
+
+pub fn is_valid_key_type(proto_type: ProtoType) -> bool {
+    !matches!(
+        proto_type,
+        ProtoType::Double
+            | ProtoType::Float
+            | ProtoType::Bytes
+            | ProtoType::Message
+            | ProtoType::Enum
+    )
+}
+
+// A map is sent as a repeated message with the key in field 1 and the value
+// in field 2, so every field whose occurrences all look like that is offered.
+pub fn find_map_field_numbers(items: &[DecodingState]) -> Vec<u128> {
+    let mut field_numbers: Vec<u128> = items.iter().map(|item| item.get_field_number()).collect();
+    field_numbers.sort_unstable();
+    field_numbers.dedup();
+
+    field_numbers
+        .into_iter()
+        .filter(|field_number| {
+            let entries: Vec<&DecodingState> = items
+                .iter()
+                .filter(|item| item.get_field_number() == *field_number)
+                .collect();
+
+            entries.len() >= MIN_MAP_ENTRIES && entries.iter().all(|entry| is_map_entry(entry))
+        })
+        .collect()
+}
+
+fn is_map_entry(state: &DecodingState) -> bool {
+    if state.get_wire_type() != WireType::LengthDelimited {
+        return false;
+    }
+
+    let items = match state.get_message_items() {
+        Some(items) => items,
+        None => return false,
+    };
+
+    let has_key = items.iter().any(|item| item.get_field_number() == 1);
+    let has_value = items.iter().any(|item| item.get_field_number() == 2);
+    let key_is_scalar = !matches!(find_field(&items, 1), Some(DecodingState::Message { .. }));
+
+    items.len() <= 2
+        && (has_key || has_value)
+        && key_is_scalar
+        && items
+            .iter()
+            .all(|item| item.get_field_number() == 1 || item.get_field_number() == 2)
+        && !(items.len() == 2 && items[0].get_field_number() == items[1].get_field_number())
+}
+
+pub fn find_field(items: &[DecodingState], field_number: u128) -> Option<&DecodingState> {
+    items
+        .iter()
+        .rev()
+        .find(|item| item.get_field_number() == field_number)
+}
@@ -0,0 +1,115 @@
+use crate::{
+    document::{DocumentValue, Reader, MAX_DEPTH},
+    error::ProtodecError,
+};
+
+pub fn decode(buffer: &[u8]) -> Result<DocumentValue, ProtodecError> {
+    let mut reader = Reader::new(buffer);
+    let value = take_value(&mut reader, 0)?;
+
+    reader.finish(value)
+}
+
+fn take_value(reader: &mut Reader<'_>, depth: usize) -> Result<DocumentValue, ProtodecError> {
+    if depth > MAX_DEPTH {
+        return Err(reader.error("Nesting is too deep".to_string()));
+    }
+
+    let format = reader.take_byte()?;
+
+    match format {
+        0x00..=0x7f => Ok(DocumentValue::Integer(format as i128)),
+        0x80..=0x8f => take_map(reader, (format & 0x0f) as u64, depth),
+        0x90..=0x9f => take_array(reader, (format & 0x0f) as u64, depth),
+        0xa0..=0xbf => take_text(reader, (format & 0x1f) as u64),
+        0xc0 => Ok(DocumentValue::Null),
+        0xc2 => Ok(DocumentValue::Bool(false)),
+        0xc3 => Ok(DocumentValue::Bool(true)),
+        0xc4..=0xc6 => {
+            let length = reader.take_uint(1 << (format - 0xc4))?;
+            let length = reader.check_length(length)?;
+            Ok(DocumentValue::Bytes(reader.take(length)?.to_vec()))
+        }
+        0xc7..=0xc9 => {
+            let length = reader.take_uint(1 << (format - 0xc7))?;
+            take_extension(reader, length)
+        }
+        0xca => Ok(DocumentValue::Float(
+            f32::from_bits(reader.take_uint(4)? as u32) as f64,
+        )),
+        0xcb => Ok(DocumentValue::Float(f64::from_bits(reader.take_uint(8)?))),
+        0xcc..=0xcf => Ok(DocumentValue::Integer(
+            reader.take_uint(1 << (format - 0xcc))? as i128,
+        )),
+        0xd0..=0xd3 => {
+            let width = 1 << (format - 0xd0);
+            let value = reader.take_uint(width)?;
+            // Sign extends the value from its width to 64 bits.
+            let shift = 64 - 8 * width as u32;
+            Ok(DocumentValue::Integer(
+                ((value << shift) as i64 >> shift) as i128,
+            ))
+        }
+        0xd4..=0xd8 => take_extension(reader, 1 << (format - 0xd4)),
+        0xd9..=0xdb => {
+            let length = reader.take_uint(1 << (format - 0xd9))?;
+            take_text(reader, length)
+        }
+        0xdc | 0xdd => {
+            let length = reader.take_uint(2 << (format - 0xdc))?;
+            take_array(reader, length, depth)
+        }
+        0xde | 0xdf => {
+            let length = reader.take_uint(2 << (format - 0xde))?;
+            take_map(reader, length, depth)
+        }
+        0xe0..=0xff => Ok(DocumentValue::Integer(format as i8 as i128)),
+        _ => Err(reader.error(format!("Unused format 0x{:02x}", format))),
+    }
+}
+
+fn take_text(reader: &mut Reader<'_>, length: u64) -> Result<DocumentValue, ProtodecError> {
+    let length = reader.check_length(length)?;
+
+    Ok(DocumentValue::Text(reader.take_text(length)?))
+}
+
+fn take_array(
+    reader: &mut Reader<'_>,
+    length: u64,
+    depth: usize,
+) -> Result<DocumentValue, ProtodecError> {
+    let length = reader.check_length(length)?;
+    let items = (0..length)
+        .map(|_| take_value(reader, depth + 1))
+        .collect::<Result<_, _>>()?;
+
+    Ok(DocumentValue::Array(items))
+}
+
+fn take_map(
+    reader: &mut Reader<'_>,
+    length: u64,
+    depth: usize,
+) -> Result<DocumentValue, ProtodecError> {
+    let length = reader.check_length(length)?;
+    let entries = (0..length)
+        .map(|_| {
+            let key = take_value(reader, depth + 1)?;
+            let value = take_value(reader, depth + 1)?;
+            Ok((key, value))
+        })
+        .collect::<Result<_, ProtodecError>>()?;
+
+    Ok(DocumentValue::Map(entries))
+}
+
+fn take_extension(reader: &mut Reader<'_>, length: u64) -> Result<DocumentValue, ProtodecError> {
+    let extension_type = reader.take_byte()? as i8;
+    let length = reader.check_length(length)?;
+
+    Ok(DocumentValue::Extension(
+        extension_type,
+        reader.take(length)?.to_vec(),
+    ))
+}
@@ -1,15 +1,39 @@
-use crate::{data::Data, decode, kind32::Kind32, kind64::Kind64, kind_varint::KindVarint};
+use crate::{
+    data::Data,
+    decode,
+    dictionary::{self, FieldDictionary, FieldEntry},
+    embedded_format::EmbeddedFormat,
+    entropy,
+    float_format::FloatFormat,
+    format,
+    integer_format::IntegerFormat,
+    interpretation::Interpretation,
+    kind32::Kind32,
+    kind64::Kind64,
+    kind_bytes::KindBytes,
+    kind_message::{self, KindMessage},
+    kind_varint::KindVarint,
+    map_view::MapView,
+    proto_type::ProtoType,
+    schema::Schema,
+    string_encoding::StringEncoding,
+    table_view::TableView,
+    validation::{self, ValidationIssue},
+    wire_type::WireType,
+};
+use std::collections::{BTreeMap, BTreeSet};
 use uuid::Uuid;
 
 pub enum ProtoDec {
     InitialState(InitialState),
-    Decoding(DecodingState),
+    Decoding(Box<Decoding>),
 }
 
 pub struct InitialState {
     pub input: String,
     pub drop_zone_active: bool,
     pub uploaded_file: Option<File>,
+    pub input_error: Option<String>,
 }
 
 impl ProtoDec {
@@ -18,6 +42,7 @@ impl ProtoDec {
             input: String::new(),
             drop_zone_active: false,
             uploaded_file: None,
+            input_error: None,
         })
     }
 }
@@ -27,6 +52,558 @@ pub struct File {
     pub buffer: Vec<u8>,
 }
 
+pub struct Decoding {
+    pub root: DecodingState,
+    pub exported_code: Option<String>,
+    pub dictionary: FieldDictionary,
+    pub dictionary_input: String,
+    pub dictionary_error: Option<String>,
+    pub annotation_editor: Option<AnnotationEditor>,
+    pub schema: Option<Schema>,
+    pub schema_input: String,
+    pub schema_error: Option<String>,
+    pub root_message_name: String,
+    pub validation_report: Option<Vec<ValidationIssue>>,
+    pub map_views: BTreeMap<Vec<u128>, MapView>,
+    pub table_views: BTreeMap<Vec<u128>, TableView>,
+    pub integer_formats: BTreeMap<Vec<u128>, IntegerFormat>,
+    pub float_formats: BTreeMap<Vec<u128>, FloatFormat>,
+    pub plain_text_views: BTreeSet<Vec<u128>>,
+    pub wire_order: bool,
+    pub collapsed_groups: BTreeSet<(Uuid, u128)>,
+    pub grouped_actions: BTreeSet<(Uuid, u128)>,
+    pub apply_to_similar: bool,
+    pub remembered_interpretations: BTreeMap<Vec<u128>, Interpretation>,
+}
+
+pub struct AnnotationEditor {
+    pub uuid: Uuid,
+    pub path: Vec<u128>,
+    pub name: String,
+    pub proto_type: String,
+    pub enum_labels: String,
+    pub bit_names: String,
+    pub note: String,
+    pub error: Option<String>,
+}
+
+impl Decoding {
+    pub fn new(root: DecodingState) -> Self {
+        Decoding {
+            root,
+            exported_code: None,
+            dictionary: FieldDictionary::default(),
+            dictionary_input: String::new(),
+            dictionary_error: None,
+            annotation_editor: None,
+            schema: None,
+            schema_input: String::new(),
+            schema_error: None,
+            root_message_name: String::new(),
+            validation_report: None,
+            map_views: BTreeMap::new(),
+            table_views: BTreeMap::new(),
+            integer_formats: BTreeMap::new(),
+            float_formats: BTreeMap::new(),
+            plain_text_views: BTreeSet::new(),
+            wire_order: false,
+            collapsed_groups: BTreeSet::new(),
+            grouped_actions: BTreeSet::new(),
+            apply_to_similar: false,
+            remembered_interpretations: BTreeMap::new(),
+        }
+    }
+
+    pub fn load_schema(&mut self) {
+        match Schema::parse(&self.schema_input) {
+            Ok(schema) => {
+                if schema.find_message(&self.root_message_name).is_none() {
+                    self.root_message_name = schema
+                        .messages
+                        .first()
+                        .map(|message| message.name.clone())
+                        .unwrap_or_default();
+                }
+
+                self.schema = Some(schema);
+                self.schema_error = None;
+                self.validation_report = None;
+                self.apply_field_entries();
+            }
+            Err(err) => self.schema_error = Some(err.message),
+        }
+    }
+
+    pub fn change_root_message(&mut self, root_message_name: String) {
+        self.root_message_name = root_message_name;
+        self.validation_report = None;
+        self.apply_field_entries();
+    }
+
+    pub fn get_root_scope(&self) -> SchemaScope<'_> {
+        root_scope(&self.root_message_name)
+    }
+
+    pub fn get_field_entry(&self, path: &[u128]) -> Option<FieldEntry> {
+        self.get_field_entry_in(self.get_root_scope(), path)
+    }
+
+    // Entries typed in the UI or loaded from a dictionary take precedence
+    // over what the schema declares for the same path.
+    pub fn get_field_entry_in(&self, scope: SchemaScope, path: &[u128]) -> Option<FieldEntry> {
+        merge_field_entry(&self.dictionary, &self.schema, scope, path)
+    }
+
+    // A field declared with a well-known type in the schema is suggested
+    // even when its content would also fit something else.
+    pub fn get_suggested_message_kind(
+        &self,
+        scope: SchemaScope,
+        path: &[u128],
+        items: &[DecodingState],
+    ) -> Option<KindMessage> {
+        self.schema
+            .as_ref()
+            .and_then(|schema| {
+                schema.find_field_by_path(scope.message_name, path.get(scope.path_offset..)?)
+            })
+            .and_then(|field| field.type_name.as_ref())
+            .and_then(|type_name| KindMessage::from_type_name(type_name))
+            .or_else(|| KindMessage::suggest(items))
+    }
+
+    fn apply_field_entries(&mut self) {
+        let (dictionary, schema, root_message_name) =
+            (&self.dictionary, &self.schema, &self.root_message_name);
+
+        self.root.apply_field_entries(
+            &|path| merge_field_entry(dictionary, schema, root_scope(root_message_name), path),
+            &[],
+        );
+
+        if let Some(schema) = schema {
+            self.root.apply_any_payload_schema(schema);
+        }
+
+        self.root
+            .apply_interpretations(&self.remembered_interpretations, &[]);
+    }
+
+    pub fn validate(&mut self) {
+        if let Some(schema) = &self.schema {
+            self.validation_report = Some(validation::validate(
+                schema,
+                &self.root_message_name,
+                &self.root,
+            ));
+        }
+    }
+
+    pub fn load_dictionary(&mut self) {
+        match FieldDictionary::parse(&self.dictionary_input) {
+            Ok(dictionary) => {
+                self.dictionary.merge(dictionary);
+                self.dictionary_error = None;
+                self.apply_field_entries();
+            }
+            Err(err) => self.dictionary_error = Some(err.message),
+        }
+    }
+
+    // Actions on an element of a repeated field are repeated for the whole
+    // group when the user asked for it.
+    pub fn get_action_targets(&self, ui_event_uuid: Uuid) -> Vec<Uuid> {
+        match self.root.find_siblings(ui_event_uuid) {
+            Some((parent_uuid, field_number, siblings))
+                if self.grouped_actions.contains(&(parent_uuid, field_number)) =>
+            {
+                siblings
+            }
+            _ => vec![ui_event_uuid],
+        }
+    }
+
+    pub fn toggle(&mut self, ui_event_uuid: Uuid) {
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.root.toggle(uuid);
+        }
+
+        self.remember_interpretation(ui_event_uuid);
+    }
+
+    pub fn select_varint_kind(&mut self, ui_event_uuid: Uuid, kind: KindVarint) {
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.root.select_varint_kind(uuid, kind);
+        }
+
+        self.remember_interpretation(ui_event_uuid);
+    }
+
+    pub fn select_kind32(&mut self, ui_event_uuid: Uuid, kind: Kind32) {
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.root.select_kind32(uuid, kind);
+        }
+
+        self.remember_interpretation(ui_event_uuid);
+    }
+
+    pub fn select_kind64(&mut self, ui_event_uuid: Uuid, kind: Kind64) {
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.root.select_kind64(uuid, kind);
+        }
+
+        self.remember_interpretation(ui_event_uuid);
+    }
+
+    pub fn select_message_kind(&mut self, ui_event_uuid: Uuid, kind: KindMessage) {
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.root.select_message_kind(uuid, kind);
+        }
+
+        self.remember_interpretation(ui_event_uuid);
+    }
+
+    pub fn decode_as_utf8_string(&mut self, ui_event_uuid: Uuid) {
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.root.decode_as_utf8_string(uuid);
+        }
+
+        self.remember_interpretation(ui_event_uuid);
+    }
+
+    pub fn decode_as_bytes(&mut self, ui_event_uuid: Uuid, kind: KindBytes) {
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.root.decode_as_bytes(uuid, kind);
+        }
+
+        self.remember_interpretation(ui_event_uuid);
+    }
+
+    // A chunk has no interpretation, so the one remembered for the path is
+    // forgotten as well.
+    pub fn decode_as_chunk(&mut self, ui_event_uuid: Uuid) {
+        let path = self.root.find_path(ui_event_uuid);
+
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.root.decode_as_chunk(uuid);
+        }
+
+        if let (true, Some(path)) = (self.apply_to_similar, path) {
+            self.forget_interpretation(&path);
+        }
+    }
+
+    pub fn decode_string_as_bytes(&mut self, ui_event_uuid: Uuid, encoding: StringEncoding) {
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.root.decode_string_as_bytes(uuid, encoding);
+        }
+    }
+
+    pub fn decode_string_as_message(&mut self, ui_event_uuid: Uuid, encoding: StringEncoding) {
+        self.decode_string_as_bytes(ui_event_uuid, encoding);
+        self.decode_as_message(ui_event_uuid);
+    }
+
+    pub fn decode_as_message(&mut self, ui_event_uuid: Uuid) {
+        for uuid in self.get_action_targets(ui_event_uuid) {
+            self.decode_one_as_message(uuid);
+        }
+
+        self.remember_interpretation(ui_event_uuid);
+    }
+
+    // The interpretation chosen for one node is remembered by its field path
+    // and given to every node at that path, including ones decoded later.
+    fn remember_interpretation(&mut self, ui_event_uuid: Uuid) {
+        if !self.apply_to_similar {
+            return;
+        }
+
+        let path = match self.root.find_path(ui_event_uuid) {
+            Some(path) => path,
+            None => return,
+        };
+
+        if let Some(interpretation) = self
+            .root
+            .find_mut(ui_event_uuid)
+            .and_then(|state| Interpretation::from_state(state))
+        {
+            self.remembered_interpretations
+                .insert(path.clone(), interpretation);
+
+            let mut similar = vec![];
+            self.root
+                .visit_path_mut(&[], &path, &mut |state| similar.push(state.get_uuid()));
+
+            for uuid in similar {
+                self.apply_to_subtree(uuid);
+            }
+        }
+    }
+
+    pub fn forget_interpretation(&mut self, path: &[u128]) {
+        self.remembered_interpretations.remove(path);
+    }
+
+    fn decode_one_as_message(&mut self, ui_event_uuid: Uuid) {
+        self.root.decode_as_message(ui_event_uuid);
+        self.apply_to_subtree(ui_event_uuid);
+    }
+
+    fn apply_to_subtree(&mut self, ui_event_uuid: Uuid) {
+        let (dictionary, schema, root_message_name, remembered_interpretations) = (
+            &self.dictionary,
+            &self.schema,
+            &self.root_message_name,
+            &self.remembered_interpretations,
+        );
+
+        if let Some(path) = self.root.find_path(ui_event_uuid) {
+            if let Some(state) = self.root.find_mut(ui_event_uuid) {
+                state.apply_field_entries(
+                    &|path| {
+                        merge_field_entry(dictionary, schema, root_scope(root_message_name), path)
+                    },
+                    &path,
+                );
+
+                if let Some(schema) = schema {
+                    state.apply_any_payload_schema(schema);
+                }
+
+                state.apply_interpretations(remembered_interpretations, &path);
+            }
+        }
+    }
+
+    // Every entry of the map is decoded in place, so the keys and values
+    // stay ordinary nodes of the tree.
+    pub fn view_as_map(&mut self, path: &[u128]) {
+        let mut entries = vec![];
+        self.root.visit_path_mut(&[], path, &mut |state| {
+            let uuid = state.get_uuid();
+            state.decode_as_message(uuid);
+            entries.push(state.clone());
+        });
+
+        let map_view = MapView::guess(&entries);
+        self.change_map_types(path, map_view);
+    }
+
+    pub fn view_as_list(&mut self, path: &[u128]) {
+        self.map_views.remove(path);
+    }
+
+    pub fn change_map_types(&mut self, path: &[u128], map_view: MapView) {
+        self.map_views.insert(path.to_vec(), map_view);
+
+        for (field_number, proto_type) in &[(1, map_view.key_type), (2, map_view.value_type)] {
+            let mut item_path = path.to_vec();
+            item_path.push(*field_number);
+
+            self.root.visit_path_mut(&[], &item_path, &mut |state| {
+                state.interpret_as(*proto_type)
+            });
+        }
+    }
+
+    pub fn view_as_table(&mut self, path: &[u128]) {
+        self.table_views.insert(path.to_vec(), TableView::default());
+    }
+
+    pub fn view_as_tree(&mut self, path: &[u128]) {
+        self.table_views.remove(path);
+    }
+
+    pub fn sort_table(&mut self, path: &[u128], column: Vec<u128>) {
+        if let Some(table_view) = self.table_views.get_mut(path) {
+            table_view.sort_by(column);
+        }
+    }
+
+    pub fn change_integer_format(&mut self, path: &[u128], integer_format: IntegerFormat) {
+        if integer_format == IntegerFormat::Decimal {
+            self.integer_formats.remove(path);
+        } else {
+            self.integer_formats.insert(path.to_vec(), integer_format);
+        }
+    }
+
+    pub fn get_formatted_integer(
+        &self,
+        state: &DecodingState,
+        scope: SchemaScope,
+        path: &[u128],
+    ) -> Option<String> {
+        let integer_format = self.integer_formats.get(path)?;
+        let (bits, width) = state.get_integer_bits()?;
+        let bit_names = self
+            .get_field_entry_in(scope, path)
+            .map(|entry| entry.bit_names)
+            .unwrap_or_default();
+
+        integer_format.format(bits, width, &bit_names)
+    }
+
+    pub fn change_float_format(&mut self, path: &[u128], float_format: FloatFormat) {
+        if float_format == FloatFormat::Shortest {
+            self.float_formats.remove(path);
+        } else {
+            self.float_formats.insert(path.to_vec(), float_format);
+        }
+    }
+
+    pub fn view_as_plain_text(&mut self, path: &[u128]) {
+        self.plain_text_views.insert(path.to_vec());
+    }
+
+    pub fn view_as_embedded_format(&mut self, path: &[u128]) {
+        self.plain_text_views.remove(path);
+    }
+
+    pub fn get_embedded_format(
+        &self,
+        state: &DecodingState,
+        path: &[u128],
+    ) -> Option<EmbeddedFormat> {
+        match state {
+            DecodingState::Utf8String { value, .. } if !self.plain_text_views.contains(path) => {
+                EmbeddedFormat::detect(value)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn get_formatted_float(&self, state: &DecodingState, path: &[u128]) -> Option<String> {
+        let float_format = self.float_formats.get(path)?;
+        let (bits, width) = state.get_float_bits()?;
+
+        float_format.format(bits, width)
+    }
+
+    pub fn start_annotation(&mut self, ui_event_uuid: Uuid) {
+        if let Some(path) = self.root.find_path(ui_event_uuid) {
+            let entry = self.dictionary.get(&path).cloned().unwrap_or_default();
+
+            self.annotation_editor = Some(AnnotationEditor {
+                uuid: ui_event_uuid,
+                path,
+                name: entry.name.unwrap_or_default(),
+                proto_type: entry
+                    .proto_type
+                    .map(|proto_type| proto_type.to_string().to_string())
+                    .unwrap_or_default(),
+                enum_labels: dictionary::format_labels(&entry.enum_labels),
+                bit_names: dictionary::format_labels(&entry.bit_names),
+                note: entry.note.unwrap_or_default(),
+                error: None,
+            });
+        }
+    }
+
+    pub fn save_annotation(&mut self) {
+        if let Some(editor) = &mut self.annotation_editor {
+            let labels =
+                dictionary::parse_enum_labels(&editor.enum_labels).and_then(|enum_labels| {
+                    Ok((enum_labels, dictionary::parse_bit_names(&editor.bit_names)?))
+                });
+            let (enum_labels, bit_names) = match labels {
+                Ok(labels) => labels,
+                Err(err) => {
+                    editor.error = Some(err.message);
+                    return;
+                }
+            };
+
+            let entry = FieldEntry {
+                name: non_empty(&editor.name),
+                proto_type: ProtoType::parse(&editor.proto_type),
+                enum_labels,
+                bit_names,
+                note: non_empty(&editor.note),
+            };
+            let path = editor.path.clone();
+            self.dictionary.set(path.clone(), entry);
+            self.annotation_editor = None;
+
+            let (dictionary, schema, root_message_name) =
+                (&self.dictionary, &self.schema, &self.root_message_name);
+            let get_field_entry = |path: &[u128]| {
+                merge_field_entry(dictionary, schema, root_scope(root_message_name), path)
+            };
+            self.root.visit_path_mut(&[], &path, &mut |state| {
+                state.apply_field_entries(&get_field_entry, &path)
+            });
+        }
+    }
+}
+
+// Fields of an unpacked Any are described by the packed message type, so
+// schema lookups start again below the payload.
+#[derive(Copy, Clone)]
+pub struct SchemaScope<'a> {
+    pub message_name: &'a str,
+    pub path_offset: usize,
+}
+
+fn root_scope(root_message_name: &str) -> SchemaScope<'_> {
+    SchemaScope {
+        message_name: root_message_name,
+        path_offset: 0,
+    }
+}
+
+fn merge_field_entry(
+    dictionary: &FieldDictionary,
+    schema: &Option<Schema>,
+    scope: SchemaScope,
+    path: &[u128],
+) -> Option<FieldEntry> {
+    let schema_entry = schema.as_ref().and_then(|schema| {
+        schema.get_field_entry(scope.message_name, path.get(scope.path_offset..)?)
+    });
+    let dictionary_entry = dictionary.get(path);
+
+    if schema_entry.is_none() && dictionary_entry.is_none() {
+        return None;
+    }
+
+    let mut entry = schema_entry.unwrap_or_default();
+    if let Some(dictionary_entry) = dictionary_entry {
+        if dictionary_entry.name.is_some() {
+            entry.name = dictionary_entry.name.clone();
+        }
+        if dictionary_entry.proto_type.is_some() {
+            entry.proto_type = dictionary_entry.proto_type;
+        }
+        entry
+            .enum_labels
+            .extend(dictionary_entry.enum_labels.clone());
+        entry.bit_names.extend(dictionary_entry.bit_names.clone());
+        entry.note = dictionary_entry.note.clone();
+    }
+
+    Some(entry)
+}
+
+fn unpack_any_payload(items: &mut [DecodingState]) {
+    for item in items.iter_mut().filter(|item| item.get_field_number() == 2) {
+        let uuid = item.get_uuid();
+        item.decode_as_message(uuid);
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum DecodingState {
     Varint {
@@ -60,6 +637,7 @@ pub enum DecodingState {
         field_number: u128,
         items: Vec<DecodingState>,
         uuid: Uuid,
+        kind: KindMessage,
     },
 
     Utf8String {
@@ -67,6 +645,13 @@ pub enum DecodingState {
         value: String,
         uuid: Uuid,
     },
+
+    Bytes {
+        field_number: u128,
+        buffer: Vec<u8>,
+        uuid: Uuid,
+        kind: KindBytes,
+    },
 }
 
 impl From<Data> for DecodingState {
@@ -118,26 +703,44 @@ impl From<Data> for DecodingState {
                 field_number,
                 items: items.iter().map(|d| Self::from(d.clone())).collect(),
                 uuid: Uuid::new_v4(),
+                kind: KindMessage::Message,
             },
         }
     }
 }
 
 impl DecodingState {
+    // Bytes shown in another interpretation, like lossy UTF-8, can still be
+    // decoded as a message.
     pub fn decode_as_message(&mut self, ui_event_uuid: Uuid) {
         match self {
             DecodingState::Chunk {
                 field_number,
                 buffer,
                 uuid,
+            }
+            | DecodingState::Bytes {
+                field_number,
+                buffer,
+                uuid,
                 ..
             } => {
                 if ui_event_uuid == *uuid {
                     if let Ok(data_items) = decode::decode_message(buffer) {
+                        let mut items: Vec<DecodingState> =
+                            data_items.iter().map(|d| Self::from(d.clone())).collect();
+                        let kind = if kind_message::get_any_type_url(&items).is_some() {
+                            unpack_any_payload(&mut items);
+                            KindMessage::Any
+                        } else {
+                            KindMessage::Message
+                        };
+
                         *self = Self::Message {
                             field_number: *field_number,
-                            items: data_items.iter().map(|d| Self::from(d.clone())).collect(),
+                            items,
                             uuid: *uuid,
+                            kind,
                         }
                     }
                 }
@@ -162,12 +765,20 @@ impl DecodingState {
                 if ui_event_uuid == *uuid {
                     let decoded_string = String::from_utf8(buffer.to_vec());
 
-                    if let Ok(decoded_string) = decoded_string {
-                        *self = Self::Utf8String {
+                    // Invalid bytes are shown highlighted instead of being
+                    // silently ignored.
+                    *self = match decoded_string {
+                        Ok(decoded_string) => Self::Utf8String {
                             field_number: *field_number,
                             value: decoded_string,
                             uuid: *uuid,
-                        }
+                        },
+                        Err(_) => Self::Bytes {
+                            field_number: *field_number,
+                            buffer: buffer.clone(),
+                            uuid: *uuid,
+                            kind: KindBytes::Utf8Lossy,
+                        },
                     }
                 }
             }
@@ -180,6 +791,83 @@ impl DecodingState {
         }
     }
 
+    // The string is replaced by a chunk of the decoded bytes, so it can be
+    // decoded further, for example as a nested message.
+    pub fn decode_string_as_bytes(&mut self, ui_event_uuid: Uuid, encoding: StringEncoding) {
+        match self {
+            DecodingState::Utf8String {
+                field_number,
+                value,
+                uuid,
+            } if ui_event_uuid == *uuid => {
+                if let Some(buffer) = encoding.decode(value) {
+                    *self = Self::Chunk {
+                        field_number: *field_number,
+                        buffer,
+                        uuid: *uuid,
+                    }
+                }
+            }
+            DecodingState::Message { items, .. } => {
+                for item in items {
+                    item.decode_string_as_bytes(ui_event_uuid, encoding);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // A chunk that does not fit the kind, like a UUID of the wrong length,
+    // stays a chunk.
+    pub fn decode_as_bytes(&mut self, ui_event_uuid: Uuid, bytes_kind: KindBytes) {
+        match self {
+            DecodingState::Chunk {
+                field_number,
+                buffer,
+                uuid,
+            } if ui_event_uuid == *uuid && bytes_kind.fits(buffer) => {
+                *self = Self::Bytes {
+                    field_number: *field_number,
+                    buffer: buffer.clone(),
+                    uuid: *uuid,
+                    kind: bytes_kind,
+                }
+            }
+            DecodingState::Bytes {
+                uuid, buffer, kind, ..
+            } if ui_event_uuid == *uuid && bytes_kind.fits(buffer) => *kind = bytes_kind,
+            DecodingState::Message { items, .. } => {
+                for item in items {
+                    item.decode_as_bytes(ui_event_uuid, bytes_kind);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn decode_as_chunk(&mut self, ui_event_uuid: Uuid) {
+        match self {
+            DecodingState::Bytes {
+                field_number,
+                buffer,
+                uuid,
+                ..
+            } if ui_event_uuid == *uuid => {
+                *self = Self::Chunk {
+                    field_number: *field_number,
+                    buffer: buffer.clone(),
+                    uuid: *uuid,
+                }
+            }
+            DecodingState::Message { items, .. } => {
+                for item in items {
+                    item.decode_as_chunk(ui_event_uuid);
+                }
+            }
+            _ => {}
+        }
+    }
+
     pub fn toggle(&mut self, ui_event_uuid: Uuid) {
         match self {
             DecodingState::Varint { uuid, kind, .. } => {
@@ -209,27 +897,358 @@ impl DecodingState {
         }
     }
 
-    pub fn get_formatted_field_number(&self) -> String {
-        let field_number = match self {
+    pub fn get_field_number(&self) -> u128 {
+        match self {
             DecodingState::Varint { field_number, .. }
             | DecodingState::Fixed64 { field_number, .. }
             | DecodingState::Fixed32 { field_number, .. }
             | DecodingState::Chunk { field_number, .. }
             | DecodingState::Message { field_number, .. }
-            | DecodingState::Utf8String { field_number, .. } => field_number,
+            | DecodingState::Utf8String { field_number, .. }
+            | DecodingState::Bytes { field_number, .. } => *field_number,
+        }
+    }
+
+    pub fn get_wire_type(&self) -> WireType {
+        match self {
+            DecodingState::Varint { .. } => WireType::Varint,
+            DecodingState::Fixed64 { .. } => WireType::Size64,
+            DecodingState::Fixed32 { .. } => WireType::Size32,
+            DecodingState::Chunk { .. }
+            | DecodingState::Message { .. }
+            | DecodingState::Utf8String { .. }
+            | DecodingState::Bytes { .. } => WireType::LengthDelimited,
+        }
+    }
+
+    pub fn get_uuid(&self) -> Uuid {
+        match self {
+            DecodingState::Varint { uuid, .. }
+            | DecodingState::Fixed64 { uuid, .. }
+            | DecodingState::Fixed32 { uuid, .. }
+            | DecodingState::Chunk { uuid, .. }
+            | DecodingState::Message { uuid, .. }
+            | DecodingState::Utf8String { uuid, .. }
+            | DecodingState::Bytes { uuid, .. } => *uuid,
+        }
+    }
+
+    pub fn find_path(&self, ui_event_uuid: Uuid) -> Option<Vec<u128>> {
+        if self.get_uuid() == ui_event_uuid {
+            return Some(vec![]);
+        }
+
+        if let DecodingState::Message { items, .. } = self {
+            for item in items {
+                if let Some(mut path) = item.find_path(ui_event_uuid) {
+                    path.insert(0, item.get_field_number());
+                    return Some(path);
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn find_siblings(&self, ui_event_uuid: Uuid) -> Option<(Uuid, u128, Vec<Uuid>)> {
+        if let DecodingState::Message { items, uuid, .. } = self {
+            if let Some(item) = items.iter().find(|item| item.get_uuid() == ui_event_uuid) {
+                let field_number = item.get_field_number();
+                let siblings = items
+                    .iter()
+                    .filter(|item| item.get_field_number() == field_number)
+                    .map(|item| item.get_uuid())
+                    .collect();
+
+                return Some((*uuid, field_number, siblings));
+            }
+
+            return items
+                .iter()
+                .find_map(|item| item.find_siblings(ui_event_uuid));
+        }
+
+        None
+    }
+
+    pub fn find_mut(&mut self, ui_event_uuid: Uuid) -> Option<&mut DecodingState> {
+        if self.get_uuid() == ui_event_uuid {
+            return Some(self);
+        }
+
+        if let DecodingState::Message { items, .. } = self {
+            for item in items {
+                if let Some(state) = item.find_mut(ui_event_uuid) {
+                    return Some(state);
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn visit_path_mut(
+        &mut self,
+        path: &[u128],
+        target_path: &[u128],
+        visitor: &mut impl FnMut(&mut DecodingState),
+    ) {
+        if path == target_path {
+            visitor(self);
+            return;
+        }
+
+        if let DecodingState::Message { items, .. } = self {
+            for item in items {
+                let mut item_path = path.to_vec();
+                item_path.push(item.get_field_number());
+
+                if target_path.starts_with(&item_path) {
+                    item.visit_path_mut(&item_path, target_path, visitor);
+                }
+            }
+        }
+    }
+
+    pub fn interpret_as(&mut self, proto_type: ProtoType) {
+        match self {
+            DecodingState::Varint { kind, .. } => match proto_type {
+                ProtoType::Sint32 | ProtoType::Sint64 => *kind = KindVarint::ZigZag,
+                ProtoType::Int32 => *kind = KindVarint::Int32,
+                ProtoType::Int64 => *kind = KindVarint::Int64,
+                ProtoType::Uint32 => *kind = KindVarint::Uint32,
+                ProtoType::Uint64 => *kind = KindVarint::Unsigned,
+                ProtoType::Bool => *kind = KindVarint::Bool,
+                ProtoType::Enum => *kind = KindVarint::Enum,
+                _ => {}
+            },
+
+            DecodingState::Fixed64 { kind, .. } => match proto_type {
+                ProtoType::Fixed64 => *kind = Kind64::Fixed64,
+                ProtoType::Sfixed64 => *kind = Kind64::SFixed64,
+                ProtoType::Double => *kind = Kind64::Double,
+                _ => {}
+            },
+
+            DecodingState::Fixed32 { kind, .. } => match proto_type {
+                ProtoType::Fixed32 => *kind = Kind32::Fixed32,
+                ProtoType::Sfixed32 => *kind = Kind32::SFixed32,
+                ProtoType::Float => *kind = Kind32::Float,
+                _ => {}
+            },
+
+            DecodingState::Chunk { uuid, .. } => {
+                let uuid = *uuid;
+                match proto_type {
+                    ProtoType::String => self.decode_as_utf8_string(uuid),
+                    ProtoType::Message => self.decode_as_message(uuid),
+                    _ => {}
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    pub fn apply_dictionary(&mut self, dictionary: &FieldDictionary, path: &[u128]) {
+        self.apply_field_entries(&|path| dictionary.get(path).cloned(), path);
+    }
+
+    pub fn apply_field_entries(
+        &mut self,
+        get_field_entry: &impl Fn(&[u128]) -> Option<FieldEntry>,
+        path: &[u128],
+    ) {
+        if let Some(proto_type) = get_field_entry(path).and_then(|entry| entry.proto_type) {
+            self.interpret_as(proto_type);
+        }
+
+        if let DecodingState::Message { items, .. } = self {
+            for item in items {
+                let mut item_path = path.to_vec();
+                item_path.push(item.get_field_number());
+                item.apply_field_entries(get_field_entry, &item_path);
+            }
+        }
+    }
+
+    // A node is interpreted before its items, so items of a chunk that the
+    // interpretation decodes as a message are visited as well.
+    pub fn apply_interpretations(
+        &mut self,
+        interpretations: &BTreeMap<Vec<u128>, Interpretation>,
+        path: &[u128],
+    ) {
+        if let Some(interpretation) = interpretations.get(path) {
+            interpretation.apply(self);
+        }
+
+        if let DecodingState::Message { items, .. } = self {
+            for item in items {
+                let mut item_path = path.to_vec();
+                item_path.push(item.get_field_number());
+                item.apply_interpretations(interpretations, &item_path);
+            }
+        }
+    }
+
+    pub fn select_varint_kind(&mut self, ui_event_uuid: Uuid, varint_kind: KindVarint) {
+        match self {
+            DecodingState::Varint { uuid, kind, .. } if ui_event_uuid == *uuid => {
+                *kind = varint_kind
+            }
+
+            DecodingState::Message { items, .. } => {
+                for item in items {
+                    item.select_varint_kind(ui_event_uuid, varint_kind);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn select_kind32(&mut self, ui_event_uuid: Uuid, kind32: Kind32) {
+        match self {
+            DecodingState::Fixed32 { uuid, kind, .. } if ui_event_uuid == *uuid => *kind = kind32,
+
+            DecodingState::Message { items, .. } => {
+                for item in items {
+                    item.select_kind32(ui_event_uuid, kind32);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn select_kind64(&mut self, ui_event_uuid: Uuid, kind64: Kind64) {
+        match self {
+            DecodingState::Fixed64 { uuid, kind, .. } if ui_event_uuid == *uuid => *kind = kind64,
+
+            DecodingState::Message { items, .. } => {
+                for item in items {
+                    item.select_kind64(ui_event_uuid, kind64);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn apply_any_payload_schema(&mut self, schema: &Schema) {
+        if let DecodingState::Message { items, kind, .. } = self {
+            if *kind == KindMessage::Any {
+                let type_name = kind_message::get_any_type_url(items)
+                    .map(|type_url| kind_message::get_any_type_name(&type_url).to_string())
+                    .filter(|type_name| schema.find_message(type_name).is_some());
+
+                if let Some(type_name) = type_name {
+                    for item in items.iter_mut().filter(|item| item.get_field_number() == 2) {
+                        item.apply_field_entries(
+                            &|path| schema.get_field_entry(&type_name, path),
+                            &[],
+                        );
+                    }
+                }
+            }
+
+            for item in items {
+                item.apply_any_payload_schema(schema);
+            }
+        }
+    }
+
+    pub fn select_message_kind(&mut self, ui_event_uuid: Uuid, message_kind: KindMessage) {
+        if let DecodingState::Message {
+            uuid, kind, items, ..
+        } = self
+        {
+            if ui_event_uuid == *uuid {
+                *kind = message_kind;
+
+                if message_kind == KindMessage::Any {
+                    unpack_any_payload(items);
+                }
+            } else {
+                for item in items {
+                    item.select_message_kind(ui_event_uuid, message_kind);
+                }
+            }
+        }
+    }
+
+    // Chunks the user has not decoded yet are decoded on the fly, so nested
+    // messages can be inspected without clicking through the whole tree.
+    pub fn get_message_items(&self) -> Option<Vec<DecodingState>> {
+        let buffer = match self {
+            DecodingState::Message { items, .. } => return Some(items.clone()),
+            // Random bytes often parse as a few fields by chance.
+            DecodingState::Chunk { buffer, .. } if !entropy::is_high_entropy(buffer) => {
+                buffer.as_slice()
+            }
+            _ => return None,
         };
 
-        format!("{:<2?}", field_number)
+        decode::decode_message(buffer)
+            .ok()
+            .map(|items| items.into_iter().map(DecodingState::from).collect())
+    }
+
+    pub fn get_integer_bits(&self) -> Option<(u64, u32)> {
+        match self {
+            DecodingState::Varint { value, kind, .. } => kind.get_integer_bits(*value),
+            DecodingState::Fixed64 { buffer, kind, .. } => kind.get_integer_bits(buffer),
+            DecodingState::Fixed32 { buffer, kind, .. } => kind.get_integer_bits(buffer),
+            _ => None,
+        }
+    }
+
+    pub fn get_float_bits(&self) -> Option<(u64, u32)> {
+        match self {
+            DecodingState::Fixed64 { buffer, kind, .. } => kind.get_float_bits(buffer),
+            DecodingState::Fixed32 { buffer, kind, .. } => kind.get_float_bits(buffer),
+            _ => None,
+        }
     }
 
-    pub fn get_formatted_kind(&self) -> String {
+    pub fn get_scalar_value_as_string(&self) -> Option<String> {
+        match self {
+            DecodingState::Varint { value, kind, .. } => Some(kind.get_value_as_string(*value)),
+            DecodingState::Fixed64 { buffer, kind, .. } => Some(kind.get_value_as_string(buffer)),
+            DecodingState::Fixed32 { buffer, kind, .. } => Some(kind.get_value_as_string(buffer)),
+            DecodingState::Utf8String { value, .. } => Some(format!("{:?}", value)),
+            DecodingState::Chunk { buffer, .. } => Some(format::format_as_hex(buffer)),
+            DecodingState::Bytes { buffer, kind, .. } => Some(kind.get_value_as_string(buffer)),
+            DecodingState::Message { .. } => None,
+        }
+    }
+
+    pub fn get_formatted_field_number(&self, field_entry: Option<&FieldEntry>) -> String {
+        match field_entry.and_then(|entry| entry.name.as_ref()) {
+            Some(name) => format!("{} ({})", name, self.get_field_number()),
+            None => format!("{:<2?}", self.get_field_number()),
+        }
+    }
+
+    pub fn get_formatted_kind(&self, field_entry: Option<&FieldEntry>) -> String {
+        let kind = self.get_formatted_wire_kind();
+
+        match field_entry.and_then(|entry| entry.proto_type) {
+            Some(proto_type) => format!("{} {}", proto_type.to_string(), kind),
+            None => kind,
+        }
+    }
+
+    fn get_formatted_wire_kind(&self) -> String {
         match self {
             DecodingState::Varint { kind, .. } => format!("<varint {}>", kind.to_string()),
             DecodingState::Fixed64 { kind, .. } => format!("<fix 64 {}>", kind.to_string()),
             DecodingState::Fixed32 { kind, .. } => format!("<fix 32 {}>", kind.to_string()),
             DecodingState::Chunk { .. } => "<chunk>".to_string(),
-            DecodingState::Message { .. } => "<msg>".to_string(),
+            DecodingState::Message { kind, .. } => match kind {
+                KindMessage::Message => "<msg>".to_string(),
+                _ => format!("<msg {}>", kind.to_string()),
+            },
             DecodingState::Utf8String { .. } => "<utf-8>".to_string(),
+            DecodingState::Bytes { kind, .. } => format!("<bytes {}>", kind.to_string()),
         }
     }
 }
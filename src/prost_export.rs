@@ -0,0 +1,507 @@
+use crate::{
+    kind32::Kind32, kind64::Kind64, kind_message::KindMessage, kind_varint::KindVarint,
+    model::DecodingState,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+const ROOT_MESSAGE_NAME: &str = "Root";
+
+// What is known about a field besides its decoded values.
+#[derive(Default)]
+pub struct FieldInfo {
+    pub name: Option<String>,
+    pub enum_labels: BTreeMap<i64, String>,
+}
+
+#[derive(Default)]
+struct MessageLayout {
+    fields: BTreeMap<u128, FieldLayout>,
+}
+
+struct FieldLayout {
+    field_type: FieldType,
+    repeated: bool,
+    name: Option<String>,
+}
+
+enum FieldType {
+    Scalar {
+        prost_type: &'static str,
+        rust_type: &'static str,
+    },
+    Message(MessageLayout),
+    Enum(BTreeMap<i64, String>),
+    WellKnown {
+        rust_type: &'static str,
+    },
+}
+
+impl MessageLayout {
+    fn from_items(
+        items: &[DecodingState],
+        path: &[u128],
+        get_field_info: &impl Fn(&[u128]) -> Option<FieldInfo>,
+    ) -> Self {
+        let mut layout = MessageLayout::default();
+        let mut occurrences: BTreeMap<u128, usize> = BTreeMap::new();
+
+        for item in items {
+            let field_number = item.get_field_number();
+            *occurrences.entry(field_number).or_insert(0) += 1;
+
+            let mut item_path = path.to_vec();
+            item_path.push(field_number);
+            let field_info = get_field_info(&item_path);
+
+            layout.merge_field(
+                field_number,
+                FieldLayout {
+                    field_type: FieldType::from_state(
+                        item,
+                        &item_path,
+                        field_info.as_ref(),
+                        get_field_info,
+                    ),
+                    repeated: false,
+                    name: field_info.and_then(|info| info.name),
+                },
+            );
+        }
+
+        for (field_number, count) in occurrences {
+            if count > 1 {
+                if let Some(field) = layout.fields.get_mut(&field_number) {
+                    field.repeated = true;
+                }
+            }
+        }
+
+        layout
+    }
+
+    fn merge(&mut self, other: MessageLayout) {
+        for (field_number, field) in other.fields {
+            self.merge_field(field_number, field);
+        }
+    }
+
+    fn merge_field(&mut self, field_number: u128, field: FieldLayout) {
+        match self.fields.get_mut(&field_number) {
+            Some(existing) => {
+                existing.repeated |= field.repeated;
+                existing.name = existing.name.take().or(field.name);
+                existing.field_type.merge(field.field_type);
+            }
+            None => {
+                self.fields.insert(field_number, field);
+            }
+        }
+    }
+}
+
+impl FieldType {
+    fn from_state(
+        state: &DecodingState,
+        path: &[u128],
+        field_info: Option<&FieldInfo>,
+        get_field_info: &impl Fn(&[u128]) -> Option<FieldInfo>,
+    ) -> Self {
+        let (prost_type, rust_type) = match state {
+            // Observed values without a label still get a variant so the
+            // generated enum covers the decoded data. Prost enums are i32, so
+            // labels outside that range are left out and an observed value
+            // outside it makes the field a plain int64.
+            DecodingState::Varint {
+                kind: KindVarint::Enum,
+                value,
+                ..
+            } if is_enum_value(*value as u64 as i64) => {
+                let mut labels: BTreeMap<i64, String> = field_info
+                    .map(|info| info.enum_labels.clone())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(value, _)| is_enum_value(*value))
+                    .collect();
+                labels
+                    .entry(*value as u64 as i64)
+                    .or_insert_with(|| format!("Value{}", *value as u64 as i64));
+
+                return FieldType::Enum(labels);
+            }
+            DecodingState::Varint { kind, .. } => match kind {
+                KindVarint::Unsigned => ("uint64", "u64"),
+                KindVarint::ZigZag => ("sint64", "i64"),
+                KindVarint::Int32 => ("int32", "i32"),
+                KindVarint::Enum | KindVarint::Int64 => ("int64", "i64"),
+                KindVarint::Uint32 => ("uint32", "u32"),
+                KindVarint::Bool => ("bool", "bool"),
+                KindVarint::Timestamp(_) => ("int64", "i64"),
+            },
+            DecodingState::Fixed64 { kind, .. } => match kind {
+                Kind64::Buffer
+                | Kind64::Fixed64
+                | Kind64::Timestamp(_)
+                | Kind64::Fixed64BigEndian => ("fixed64", "u64"),
+                Kind64::SFixed64 | Kind64::SFixed64BigEndian => ("sfixed64", "i64"),
+                Kind64::Double | Kind64::DoubleBigEndian => ("double", "f64"),
+            },
+            DecodingState::Fixed32 { kind, .. } => match kind {
+                Kind32::Buffer
+                | Kind32::Fixed32
+                | Kind32::Timestamp(_)
+                | Kind32::Fixed32BigEndian
+                | Kind32::Ipv4 => ("fixed32", "u32"),
+                Kind32::SFixed32 | Kind32::SFixed32BigEndian => ("sfixed32", "i32"),
+                Kind32::Float | Kind32::FloatBigEndian => ("float", "f32"),
+            },
+            DecodingState::Chunk { .. } | DecodingState::Bytes { .. } => {
+                ("bytes = \"vec\"", "Vec<u8>")
+            }
+            DecodingState::Utf8String { .. } => ("string", "String"),
+            DecodingState::Message { items, kind, .. } => {
+                return match well_known_rust_type(*kind) {
+                    Some(rust_type) => FieldType::WellKnown { rust_type },
+                    None => {
+                        FieldType::Message(MessageLayout::from_items(items, path, get_field_info))
+                    }
+                }
+            }
+        };
+
+        FieldType::Scalar {
+            prost_type,
+            rust_type,
+        }
+    }
+
+    // A field may be decoded in one occurrence and left as a raw chunk in
+    // another, so the most specific interpretation seen so far wins.
+    fn rank(&self) -> u8 {
+        match self {
+            FieldType::WellKnown { .. } => 3,
+            FieldType::Message(_) => 2,
+            FieldType::Enum(_) => 1,
+            FieldType::Scalar {
+                prost_type: "string",
+                ..
+            } => 1,
+            FieldType::Scalar { .. } => 0,
+        }
+    }
+
+    fn merge(&mut self, other: FieldType) {
+        match (self, other) {
+            (FieldType::Message(layout), FieldType::Message(other_layout)) => {
+                layout.merge(other_layout)
+            }
+            (FieldType::Enum(labels), FieldType::Enum(other_labels)) => {
+                for (value, label) in other_labels {
+                    labels.entry(value).or_insert(label);
+                }
+            }
+            // A value that doesn't fit the enum must not be dropped.
+            (
+                this @ FieldType::Enum(_),
+                other @ FieldType::Scalar {
+                    prost_type: "int64",
+                    ..
+                },
+            ) => *this = other,
+            (this, other) => {
+                if other.rank() > this.rank() {
+                    *this = other;
+                }
+            }
+        }
+    }
+}
+
+// Names and enum labels are used where they are known, the rest is named by
+// field numbers.
+pub fn export_as_prost_structs(
+    root: &DecodingState,
+    get_field_info: &impl Fn(&[u128]) -> Option<FieldInfo>,
+) -> String {
+    match root {
+        DecodingState::Message { items, .. } => {
+            let mut output = String::new();
+            write_message(
+                &mut output,
+                ROOT_MESSAGE_NAME,
+                &MessageLayout::from_items(items, &[], get_field_info),
+                0,
+            );
+            output
+        }
+        _ => "// Decode the root chunk as a message to export its layout.\n".to_string(),
+    }
+}
+
+fn write_message(output: &mut String, name: &str, layout: &MessageLayout, depth: usize) {
+    let indent = "    ".repeat(depth);
+    let module_name = to_snake_case(name);
+    let names = get_field_names(layout);
+
+    writeln!(
+        output,
+        "{}#[derive(Clone, PartialEq, ::prost::Message)]",
+        indent
+    )
+    .unwrap();
+    writeln!(output, "{}pub struct {} {{", indent, name).unwrap();
+
+    for (field_number, field) in &layout.fields {
+        let (field_name, type_name) = &names[field_number];
+        let (prost_type, rust_type) = match &field.field_type {
+            FieldType::Scalar {
+                prost_type,
+                rust_type,
+            } => (prost_type.to_string(), rust_type.to_string()),
+            FieldType::Message(_) => (
+                "message".to_string(),
+                format!("{}::{}", module_name, type_name),
+            ),
+            FieldType::Enum(_) => (
+                format!("enumeration = \"{}::{}\"", module_name, type_name),
+                "i32".to_string(),
+            ),
+            FieldType::WellKnown { rust_type } => ("message".to_string(), rust_type.to_string()),
+        };
+
+        let (label, rust_type) = match (&field.field_type, field.repeated) {
+            (_, true) => (", repeated", format!("Vec<{}>", rust_type)),
+            (FieldType::Message(_), false) | (FieldType::WellKnown { .. }, false) => {
+                (", optional", format!("Option<{}>", rust_type))
+            }
+            (FieldType::Scalar { .. }, false) | (FieldType::Enum(_), false) => ("", rust_type),
+        };
+
+        writeln!(
+            output,
+            "{}    #[prost({}{}, tag = \"{}\")]",
+            indent, prost_type, label, field_number
+        )
+        .unwrap();
+        writeln!(output, "{}    pub {}: {},", indent, field_name, rust_type).unwrap();
+    }
+
+    writeln!(output, "{}}}", indent).unwrap();
+
+    let nested_types: Vec<(&String, &FieldType)> = layout
+        .fields
+        .iter()
+        .filter(|(_, field)| matches!(field.field_type, FieldType::Message(_) | FieldType::Enum(_)))
+        .map(|(field_number, field)| (&names[field_number].1, &field.field_type))
+        .collect();
+
+    if nested_types.is_empty() {
+        return;
+    }
+
+    writeln!(
+        output,
+        "{}/// Nested message and enum types in `{}`.",
+        indent, name
+    )
+    .unwrap();
+    writeln!(output, "{}pub mod {} {{", indent, module_name).unwrap();
+
+    for (type_name, field_type) in nested_types {
+        match field_type {
+            FieldType::Message(layout) => write_message(output, type_name, layout, depth + 1),
+            FieldType::Enum(labels) => write_enum(output, type_name, labels, depth + 1),
+            FieldType::Scalar { .. } | FieldType::WellKnown { .. } => {}
+        }
+    }
+
+    writeln!(output, "{}}}", indent).unwrap();
+}
+
+fn write_enum(output: &mut String, name: &str, labels: &BTreeMap<i64, String>, depth: usize) {
+    let indent = "    ".repeat(depth);
+    let mut used_names = BTreeSet::new();
+
+    writeln!(
+        output,
+        "{}#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]",
+        indent
+    )
+    .unwrap();
+    writeln!(output, "{}#[repr(i32)]", indent).unwrap();
+    writeln!(output, "{}pub enum {} {{", indent, name).unwrap();
+
+    for (value, label) in labels {
+        let variant_name = to_pascal_case(label)
+            .filter(|variant_name| !used_names.contains(variant_name))
+            .unwrap_or_else(|| format!("Value{}", value).replace('-', "Minus"));
+        let variant_name = make_unique(variant_name, "", &mut used_names);
+
+        writeln!(output, "{}    {} = {},", indent, variant_name, value).unwrap();
+    }
+
+    writeln!(output, "{}}}", indent).unwrap();
+}
+
+// Returns the field and the nested type name of every field. A name that is
+// unknown, not a valid identifier or already taken falls back to the field
+// number.
+fn get_field_names(layout: &MessageLayout) -> BTreeMap<u128, (String, String)> {
+    let mut used_field_names = BTreeSet::new();
+    let mut used_type_names = BTreeSet::new();
+
+    layout
+        .fields
+        .iter()
+        .map(|(field_number, field)| {
+            let field_name = field
+                .name
+                .as_deref()
+                .and_then(to_field_name)
+                .filter(|field_name| !used_field_names.contains(field_name))
+                .unwrap_or_else(|| format!("field_{}", field_number));
+            let type_name = field
+                .name
+                .as_deref()
+                .and_then(to_pascal_case)
+                .filter(|type_name| !used_type_names.contains(type_name))
+                .unwrap_or_else(|| format!("Field{}", field_number));
+            let field_name = make_unique(field_name, "_", &mut used_field_names);
+            let type_name = make_unique(type_name, "", &mut used_type_names);
+
+            (*field_number, (field_name, type_name))
+        })
+        .collect()
+}
+
+// A fallback name can still clash with a known name, so a numeric suffix is
+// added until the name is free.
+fn make_unique(name: String, separator: &str, used_names: &mut BTreeSet<String>) -> String {
+    let mut unique_name = name.clone();
+    let mut suffix = 2;
+
+    while !used_names.insert(unique_name.clone()) {
+        unique_name = format!("{}{}{}", name, separator, suffix);
+        suffix += 1;
+    }
+
+    unique_name
+}
+
+fn is_enum_value(value: i64) -> bool {
+    (i64::from(i32::MIN)..=i64::from(i32::MAX)).contains(&value)
+}
+
+// Prost maps wrapper types to optional scalars and the rest to the
+// prost-types crate.
+fn well_known_rust_type(kind: KindMessage) -> Option<&'static str> {
+    match kind {
+        KindMessage::Message => None,
+        KindMessage::Timestamp => Some("::prost_types::Timestamp"),
+        KindMessage::Duration => Some("::prost_types::Duration"),
+        KindMessage::DoubleValue => Some("f64"),
+        KindMessage::FloatValue => Some("f32"),
+        KindMessage::Int64Value => Some("i64"),
+        KindMessage::UInt64Value => Some("u64"),
+        KindMessage::Int32Value => Some("i32"),
+        KindMessage::UInt32Value => Some("u32"),
+        KindMessage::BoolValue => Some("bool"),
+        KindMessage::StringValue => Some("String"),
+        KindMessage::BytesValue => Some("Vec<u8>"),
+        KindMessage::Struct => Some("::prost_types::Struct"),
+        KindMessage::Value => Some("::prost_types::Value"),
+        KindMessage::ListValue => Some("::prost_types::ListValue"),
+        KindMessage::Any => Some("::prost_types::Any"),
+    }
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+// Splits names like "userId", "user_id" or "USER ID" into lowercase words.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    let mut previous: Option<char> = None;
+
+    for ch in name.chars() {
+        if !ch.is_ascii_alphanumeric() {
+            previous = None;
+            continue;
+        }
+
+        let starts_word = match previous {
+            None => true,
+            Some(previous) => ch.is_uppercase() && !previous.is_uppercase(),
+        };
+
+        if starts_word || words.is_empty() {
+            words.push(String::new());
+        }
+        if let Some(word) = words.last_mut() {
+            word.push(ch.to_ascii_lowercase());
+        }
+        previous = Some(ch);
+    }
+
+    words
+}
+
+fn starts_with_letter(name: &str) -> bool {
+    name.chars()
+        .next()
+        .map(|ch| ch.is_ascii_alphabetic())
+        .unwrap_or(false)
+}
+
+fn to_field_name(name: &str) -> Option<String> {
+    let field_name = split_words(name).join("_");
+
+    if !starts_with_letter(&field_name) {
+        None
+    } else if ["self", "super", "crate", "extern"].contains(&field_name.as_str()) {
+        Some(format!("{}_", field_name))
+    } else if RUST_KEYWORDS.contains(&field_name.as_str()) {
+        Some(format!("r#{}", field_name))
+    } else {
+        Some(field_name)
+    }
+}
+
+fn to_pascal_case(name: &str) -> Option<String> {
+    let pascal_case: String = split_words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+
+    if starts_with_letter(&pascal_case) && pascal_case != "Self" {
+        Some(pascal_case)
+    } else {
+        None
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+
+    for (index, ch) in name.chars().enumerate() {
+        if ch.is_uppercase() {
+            if index > 0 {
+                result.push('_');
+            }
+            result.extend(ch.to_lowercase());
+        } else {
+            result.push(ch);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,107 @@
+use crate::wire_type::WireType;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ProtoType {
+    Double,
+    Float,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    Sint32,
+    Sint64,
+    Fixed32,
+    Fixed64,
+    Sfixed32,
+    Sfixed64,
+    Bool,
+    String,
+    Bytes,
+    Message,
+    Enum,
+}
+
+impl ProtoType {
+    pub fn all() -> &'static [ProtoType] {
+        &[
+            ProtoType::Double,
+            ProtoType::Float,
+            ProtoType::Int32,
+            ProtoType::Int64,
+            ProtoType::Uint32,
+            ProtoType::Uint64,
+            ProtoType::Sint32,
+            ProtoType::Sint64,
+            ProtoType::Fixed32,
+            ProtoType::Fixed64,
+            ProtoType::Sfixed32,
+            ProtoType::Sfixed64,
+            ProtoType::Bool,
+            ProtoType::String,
+            ProtoType::Bytes,
+            ProtoType::Message,
+            ProtoType::Enum,
+        ]
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "double" => Some(ProtoType::Double),
+            "float" => Some(ProtoType::Float),
+            "int32" => Some(ProtoType::Int32),
+            "int64" => Some(ProtoType::Int64),
+            "uint32" => Some(ProtoType::Uint32),
+            "uint64" => Some(ProtoType::Uint64),
+            "sint32" => Some(ProtoType::Sint32),
+            "sint64" => Some(ProtoType::Sint64),
+            "fixed32" => Some(ProtoType::Fixed32),
+            "fixed64" => Some(ProtoType::Fixed64),
+            "sfixed32" => Some(ProtoType::Sfixed32),
+            "sfixed64" => Some(ProtoType::Sfixed64),
+            "bool" => Some(ProtoType::Bool),
+            "string" => Some(ProtoType::String),
+            "bytes" => Some(ProtoType::Bytes),
+            "message" => Some(ProtoType::Message),
+            "enum" => Some(ProtoType::Enum),
+            _ => None,
+        }
+    }
+
+    pub fn to_string(self) -> &'static str {
+        match self {
+            ProtoType::Double => "double",
+            ProtoType::Float => "float",
+            ProtoType::Int32 => "int32",
+            ProtoType::Int64 => "int64",
+            ProtoType::Uint32 => "uint32",
+            ProtoType::Uint64 => "uint64",
+            ProtoType::Sint32 => "sint32",
+            ProtoType::Sint64 => "sint64",
+            ProtoType::Fixed32 => "fixed32",
+            ProtoType::Fixed64 => "fixed64",
+            ProtoType::Sfixed32 => "sfixed32",
+            ProtoType::Sfixed64 => "sfixed64",
+            ProtoType::Bool => "bool",
+            ProtoType::String => "string",
+            ProtoType::Bytes => "bytes",
+            ProtoType::Message => "message",
+            ProtoType::Enum => "enum",
+        }
+    }
+
+    pub fn wire_type(self) -> WireType {
+        match self {
+            ProtoType::Int32
+            | ProtoType::Int64
+            | ProtoType::Uint32
+            | ProtoType::Uint64
+            | ProtoType::Sint32
+            | ProtoType::Sint64
+            | ProtoType::Bool
+            | ProtoType::Enum => WireType::Varint,
+            ProtoType::Fixed64 | ProtoType::Sfixed64 | ProtoType::Double => WireType::Size64,
+            ProtoType::Fixed32 | ProtoType::Sfixed32 | ProtoType::Float => WireType::Size32,
+            ProtoType::String | ProtoType::Bytes | ProtoType::Message => WireType::LengthDelimited,
+        }
+    }
+}
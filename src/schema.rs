@@ -0,0 +1,484 @@
+use crate::{dictionary::FieldEntry, error::ProtodecError, proto_type::ProtoType};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    pub messages: Vec<MessageDescriptor>,
+    pub enums: Vec<EnumDescriptor>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageDescriptor {
+    pub name: String,
+    pub fields: Vec<FieldDescriptor>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldDescriptor {
+    pub name: String,
+    pub number: u128,
+    pub repeated: bool,
+    pub proto_type: ProtoType,
+    pub type_name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumDescriptor {
+    pub name: String,
+    pub values: BTreeMap<i64, String>,
+}
+
+impl Schema {
+    pub fn parse(source: &str) -> Result<Self, ProtodecError> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+            schema: Schema::default(),
+        };
+
+        parser.parse_file()?;
+        parser.schema.resolve_type_names();
+
+        Ok(parser.schema)
+    }
+
+    pub fn find_message(&self, name: &str) -> Option<&MessageDescriptor> {
+        let name = name.trim_start_matches('.');
+        self.messages.iter().find(|message| message.name == name)
+    }
+
+    pub fn find_enum(&self, name: &str) -> Option<&EnumDescriptor> {
+        let name = name.trim_start_matches('.');
+        self.enums
+            .iter()
+            .find(|enumeration| enumeration.name == name)
+    }
+
+    pub fn find_field_by_path(
+        &self,
+        root_message_name: &str,
+        path: &[u128],
+    ) -> Option<&FieldDescriptor> {
+        let (last, parents) = path.split_last()?;
+        let mut descriptor = self.find_message(root_message_name)?;
+
+        for field_number in parents {
+            let type_name = descriptor.find_field(*field_number)?.type_name.as_ref()?;
+            descriptor = self.find_message(type_name)?;
+        }
+
+        descriptor.find_field(*last)
+    }
+
+    pub fn get_field_entry(&self, root_message_name: &str, path: &[u128]) -> Option<FieldEntry> {
+        let field = self.find_field_by_path(root_message_name, path)?;
+
+        let enum_labels = field
+            .type_name
+            .as_ref()
+            .and_then(|type_name| self.find_enum(type_name))
+            .map(|enumeration| enumeration.values.clone())
+            .unwrap_or_default();
+
+        Some(FieldEntry {
+            name: Some(field.name.clone()),
+            proto_type: Some(field.proto_type),
+            enum_labels,
+            bit_names: BTreeMap::new(),
+            note: None,
+        })
+    }
+
+    fn resolve_type_names(&mut self) {
+        let message_names: Vec<String> = self.messages.iter().map(|m| m.name.clone()).collect();
+        let enum_names: Vec<String> = self.enums.iter().map(|e| e.name.clone()).collect();
+
+        for message in &mut self.messages {
+            for field in &mut message.fields {
+                if let Some(type_name) = &field.type_name {
+                    for candidate in scoped_candidates(&message.name, type_name) {
+                        if message_names.contains(&candidate) {
+                            field.proto_type = ProtoType::Message;
+                            field.type_name = Some(candidate);
+                            break;
+                        }
+                        if enum_names.contains(&candidate) {
+                            field.proto_type = ProtoType::Enum;
+                            field.type_name = Some(candidate);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl MessageDescriptor {
+    pub fn find_field(&self, number: u128) -> Option<&FieldDescriptor> {
+        self.fields.iter().find(|field| field.number == number)
+    }
+}
+
+// Mirrors protoc name resolution: the innermost scope is searched first,
+// then every enclosing scope up to the package root.
+fn scoped_candidates(scope: &str, type_name: &str) -> Vec<String> {
+    if type_name.starts_with('.') {
+        return vec![type_name.trim_start_matches('.').to_string()];
+    }
+
+    let mut candidates = vec![];
+    let mut scope = scope.to_string();
+
+    loop {
+        if scope.is_empty() {
+            candidates.push(type_name.to_string());
+            break;
+        }
+
+        candidates.push(format!("{}.{}", scope, type_name));
+
+        scope = match scope.rfind('.') {
+            Some(index) => scope[..index].to_string(),
+            None => String::new(),
+        };
+    }
+
+    candidates
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    position: usize,
+    schema: Schema,
+}
+
+impl Parser {
+    fn parse_file(&mut self) -> Result<(), ProtodecError> {
+        let mut package = String::new();
+
+        while let Some(token) = self.next() {
+            match token.as_str() {
+                "package" => {
+                    package = self.expect_identifier()?;
+                    self.expect(";")?;
+                }
+                "message" => self.parse_message(&package)?,
+                "enum" => self.parse_enum(&package)?,
+                "service" | "extend" => self.skip_block()?,
+                "syntax" | "import" | "option" => self.skip_statement()?,
+                ";" => {}
+                _ => return Err(self.error(&token)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_message(&mut self, scope: &str) -> Result<(), ProtodecError> {
+        let name = qualify(scope, &self.expect_identifier()?);
+        self.expect("{")?;
+
+        let index = self.schema.messages.len();
+        self.schema.messages.push(MessageDescriptor {
+            name: name.clone(),
+            fields: vec![],
+        });
+
+        let mut fields = vec![];
+        self.parse_message_body(&name, &mut fields)?;
+        self.schema.messages[index].fields = fields;
+
+        Ok(())
+    }
+
+    fn parse_message_body(
+        &mut self,
+        name: &str,
+        fields: &mut Vec<FieldDescriptor>,
+    ) -> Result<(), ProtodecError> {
+        loop {
+            let token = self.next().ok_or_else(|| self.unexpected_end())?;
+
+            match token.as_str() {
+                "}" => return Ok(()),
+                "message" => self.parse_message(name)?,
+                "enum" => self.parse_enum(name)?,
+                "oneof" => {
+                    self.expect_identifier()?;
+                    self.expect("{")?;
+                    self.parse_message_body(name, fields)?;
+                }
+                "extend" => self.skip_block()?,
+                "option" | "reserved" | "extensions" => self.skip_statement()?,
+                ";" => {}
+                "map" => fields.push(self.parse_map_field(name)?),
+                "repeated" => {
+                    let type_name = self.expect_identifier()?;
+                    fields.push(self.parse_field(type_name, true)?);
+                }
+                "optional" | "required" => {
+                    let type_name = self.expect_identifier()?;
+                    fields.push(self.parse_field(type_name, false)?);
+                }
+                _ => fields.push(self.parse_field(token, false)?),
+            }
+        }
+    }
+
+    fn parse_field(
+        &mut self,
+        type_name: String,
+        repeated: bool,
+    ) -> Result<FieldDescriptor, ProtodecError> {
+        let name = self.expect_identifier()?;
+        self.expect("=")?;
+        let number = self.expect_number()? as u128;
+        self.skip_statement()?;
+
+        Ok(describe_field(name, number, repeated, type_name))
+    }
+
+    // A map field is sent as a repeated message with the key in field 1
+    // and the value in field 2, so it is described the same way.
+    fn parse_map_field(&mut self, scope: &str) -> Result<FieldDescriptor, ProtodecError> {
+        self.expect("<")?;
+        let key_type = self.expect_identifier()?;
+        self.expect(",")?;
+        let value_type = self.expect_identifier()?;
+        self.expect(">")?;
+
+        let name = self.expect_identifier()?;
+        self.expect("=")?;
+        let number = self.expect_number()? as u128;
+        self.skip_statement()?;
+
+        let entry_name = qualify(scope, &format!("{}Entry", to_camel_case(&name)));
+        self.schema.messages.push(MessageDescriptor {
+            name: entry_name.clone(),
+            fields: vec![
+                describe_field("key".into(), 1, false, key_type),
+                describe_field("value".into(), 2, false, value_type),
+            ],
+        });
+
+        Ok(describe_field(
+            name,
+            number,
+            true,
+            format!(".{}", entry_name),
+        ))
+    }
+
+    fn parse_enum(&mut self, scope: &str) -> Result<(), ProtodecError> {
+        let name = qualify(scope, &self.expect_identifier()?);
+        self.expect("{")?;
+
+        let mut values = BTreeMap::new();
+        loop {
+            let token = self.next().ok_or_else(|| self.unexpected_end())?;
+
+            match token.as_str() {
+                "}" => break,
+                "option" | "reserved" => self.skip_statement()?,
+                ";" => {}
+                _ => {
+                    self.expect("=")?;
+                    let value = self.expect_number()?;
+                    self.skip_statement()?;
+                    values.entry(value).or_insert(token);
+                }
+            }
+        }
+
+        self.schema.enums.push(EnumDescriptor { name, values });
+        Ok(())
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), ProtodecError> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(self.error(&token)),
+            None => Err(self.unexpected_end()),
+        }
+    }
+
+    fn expect_identifier(&mut self) -> Result<String, ProtodecError> {
+        match self.next() {
+            Some(token) if is_identifier(&token) => Ok(token),
+            Some(token) => Err(self.error(&token)),
+            None => Err(self.unexpected_end()),
+        }
+    }
+
+    fn expect_number(&mut self) -> Result<i64, ProtodecError> {
+        let token = self.next().ok_or_else(|| self.unexpected_end())?;
+
+        let negative = token.starts_with('-');
+        let digits = token.trim_start_matches('-');
+
+        let number = if digits.starts_with("0x") || digits.starts_with("0X") {
+            i64::from_str_radix(&digits[2..], 16)
+        } else {
+            digits.parse::<i64>()
+        }
+        .map_err(|_| self.error(&token))?;
+
+        Ok(if negative { -number } else { number })
+    }
+
+    // Skips the rest of a statement including field options like
+    // `[packed = true]`.
+    fn skip_statement(&mut self) -> Result<(), ProtodecError> {
+        loop {
+            match self.next() {
+                Some(token) if token == ";" => return Ok(()),
+                Some(_) => {}
+                None => return Err(self.unexpected_end()),
+            }
+        }
+    }
+
+    fn skip_block(&mut self) -> Result<(), ProtodecError> {
+        let mut depth = 0;
+
+        loop {
+            match self.next() {
+                Some(token) if token == "{" => depth += 1,
+                Some(token) if token == "}" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                Some(_) => {}
+                None => return Err(self.unexpected_end()),
+            }
+        }
+    }
+
+    fn error(&self, token: &str) -> ProtodecError {
+        ProtodecError {
+            message: format!(
+                "Error. Unexpected `{}` in schema at token {}.",
+                token, self.position
+            ),
+        }
+    }
+
+    fn unexpected_end(&self) -> ProtodecError {
+        ProtodecError {
+            message: "Error. Unexpected end of schema.".into(),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<String>, ProtodecError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = vec![];
+    let mut index = 0;
+
+    while index < chars.len() {
+        let ch = chars[index];
+
+        if ch.is_whitespace() {
+            index += 1;
+        } else if ch == '/' && chars.get(index + 1) == Some(&'/') {
+            while index < chars.len() && chars[index] != '\n' {
+                index += 1;
+            }
+        } else if ch == '/' && chars.get(index + 1) == Some(&'*') {
+            index += 2;
+            while index < chars.len()
+                && !(chars[index] == '*' && chars.get(index + 1) == Some(&'/'))
+            {
+                index += 1;
+            }
+            index += 2;
+        } else if ch == '"' || ch == '\'' {
+            let start = index;
+            index += 1;
+            while index < chars.len() && chars[index] != ch {
+                if chars[index] == '\\' {
+                    index += 1;
+                }
+                index += 1;
+            }
+            if index >= chars.len() {
+                return Err(ProtodecError {
+                    message: "Error. Unterminated string in schema.".into(),
+                });
+            }
+            index += 1;
+            tokens.push(chars[start..index].iter().collect());
+        } else if is_word_char(ch)
+            || (ch == '-' && matches!(chars.get(index + 1), Some(c) if c.is_ascii_digit()))
+        {
+            let start = index;
+            index += 1;
+            while index < chars.len() && is_word_char(chars[index]) {
+                index += 1;
+            }
+            tokens.push(chars[start..index].iter().collect());
+        } else {
+            tokens.push(ch.to_string());
+            index += 1;
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn describe_field(
+    name: String,
+    number: u128,
+    repeated: bool,
+    type_name: String,
+) -> FieldDescriptor {
+    let (proto_type, type_name) = match ProtoType::parse(&type_name) {
+        Some(proto_type) => (proto_type, None),
+        None => (ProtoType::Message, Some(type_name)),
+    };
+
+    FieldDescriptor {
+        name,
+        number,
+        repeated,
+        proto_type,
+        type_name,
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '.'
+}
+
+fn is_identifier(token: &str) -> bool {
+    matches!(token.chars().next(), Some(ch) if ch.is_alphabetic() || ch == '_' || ch == '.')
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,71 @@
+use crate::{base64, data::Data, decode, entropy, hex};
+
+const MAX_FIELD_NUMBER: u128 = (1 << 29) - 1;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StringEncoding {
+    Base64,
+    Hex,
+}
+
+impl StringEncoding {
+    // Hex goes first, as most hex strings are valid base64 as well.
+    pub fn all() -> &'static [StringEncoding] {
+        &[StringEncoding::Hex, StringEncoding::Base64]
+    }
+
+    pub fn to_string(self) -> &'static str {
+        match self {
+            StringEncoding::Base64 => "base64",
+            StringEncoding::Hex => "hex",
+        }
+    }
+
+    pub fn decode(self, source: &str) -> Option<Vec<u8>> {
+        let buffer = match self {
+            StringEncoding::Base64 => base64::decode(source)?,
+            StringEncoding::Hex => hex::decode(source)?,
+        };
+
+        if buffer.is_empty() {
+            None
+        } else {
+            Some(buffer)
+        }
+    }
+
+    // Suggested only for a single word whose bytes parse as a message with
+    // sane field numbers, so most plain text that happens to be base64 is
+    // skipped.
+    pub fn suggest(source: &str) -> Option<Self> {
+        if source.trim().contains(char::is_whitespace) {
+            return None;
+        }
+
+        StringEncoding::all().iter().copied().find(|encoding| {
+            matches!(encoding.decode(source), Some(buffer) if is_plausible_message(&buffer))
+        })
+    }
+}
+
+fn is_plausible_message(buffer: &[u8]) -> bool {
+    // Encrypted or compressed bytes often parse as a few fields by chance.
+    if buffer.len() < 2 || entropy::is_high_entropy(buffer) {
+        return false;
+    }
+
+    match decode::decode_message(buffer) {
+        Ok(items) => items.iter().all(|item| {
+            let field_number = match item {
+                Data::Varint { field_number, .. }
+                | Data::Fixed64 { field_number, .. }
+                | Data::Fixed32 { field_number, .. }
+                | Data::Chunk { field_number, .. }
+                | Data::Message { field_number, .. } => *field_number,
+            };
+
+            (1..=MAX_FIELD_NUMBER).contains(&field_number)
+        }),
+        Err(_) => false,
+    }
+}
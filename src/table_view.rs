@@ -0,0 +1,174 @@
+use crate::model::DecodingState;
+use std::cmp::Ordering;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableView {
+    pub sort_column: Option<Vec<u128>>,
+    pub descending: bool,
+}
+
+impl TableView {
+    pub fn sort_by(&mut self, column: Vec<u128>) {
+        if self.sort_column.as_ref() == Some(&column) {
+            self.descending = !self.descending;
+        } else {
+            self.sort_column = Some(column);
+            self.descending = false;
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Table {
+    pub columns: Vec<Vec<u128>>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    // Every element has to be a message, or a chunk that decodes as one.
+    pub fn fits(elements: &[&DecodingState]) -> bool {
+        elements
+            .iter()
+            .all(|element| element.get_message_items().is_some())
+    }
+
+    // Nested messages are flattened, so every scalar field becomes a column
+    // named by its path inside the element.
+    pub fn from_elements(
+        elements: &[&DecodingState],
+        format_value: impl Fn(&DecodingState, &[u128]) -> String,
+    ) -> Self {
+        let mut columns: Vec<Vec<u128>> = vec![];
+        let mut cells: Vec<Vec<(Vec<u128>, String)>> = vec![];
+
+        for element in elements {
+            let mut row = vec![];
+            if let Some(items) = element.get_message_items() {
+                collect_cells(&items, &[], &format_value, &mut row);
+            }
+
+            for (column, _) in &row {
+                if !columns.contains(column) {
+                    columns.push(column.clone());
+                }
+            }
+            cells.push(row);
+        }
+
+        columns.sort();
+
+        let rows = cells
+            .into_iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| {
+                        row.iter()
+                            .filter(|(path, _)| path == column)
+                            .map(|(_, value)| value.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Table { columns, rows }
+    }
+
+    pub fn sort(&mut self, table_view: &TableView) {
+        let index = match table_view
+            .sort_column
+            .as_ref()
+            .and_then(|column| self.columns.iter().position(|c| c == column))
+        {
+            Some(index) => index,
+            None => return,
+        };
+
+        self.rows.sort_by(|a, b| {
+            let ordering = compare_cells(&a[index], &b[index]);
+            if table_view.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    pub fn to_csv(&self, column_titles: &[String]) -> String {
+        let mut lines = vec![column_titles
+            .iter()
+            .map(|title| escape_csv(title))
+            .collect::<Vec<_>>()
+            .join(",")];
+
+        for row in &self.rows {
+            lines.push(
+                row.iter()
+                    .map(|cell| escape_csv(cell))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+
+        lines.join("\r\n") + "\r\n"
+    }
+}
+
+fn collect_cells(
+    items: &[DecodingState],
+    path: &[u128],
+    format_value: &impl Fn(&DecodingState, &[u128]) -> String,
+    row: &mut Vec<(Vec<u128>, String)>,
+) {
+    for item in items {
+        let mut item_path = path.to_vec();
+        item_path.push(item.get_field_number());
+
+        match item {
+            DecodingState::Message { items, kind, .. }
+                if kind.get_value_as_string(items).is_none() =>
+            {
+                collect_cells(items, &item_path, format_value, row)
+            }
+            _ => {
+                let value = format_value(item, &item_path);
+                row.push((item_path, value));
+            }
+        }
+    }
+}
+
+enum CellKey<'a> {
+    Number(f64),
+    Text(&'a str),
+}
+
+impl<'a> CellKey<'a> {
+    fn from_cell(cell: &'a str) -> Self {
+        match cell.parse::<f64>() {
+            Ok(number) => CellKey::Number(number),
+            Err(_) => CellKey::Text(cell),
+        }
+    }
+}
+
+// Numbers are compared by value so that 10 goes after 9, and go before all
+// text, so that mixed columns still have a consistent order.
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (CellKey::from_cell(a), CellKey::from_cell(b)) {
+        (CellKey::Number(a), CellKey::Number(b)) => a.total_cmp(&b),
+        (CellKey::Number(_), CellKey::Text(_)) => Ordering::Less,
+        (CellKey::Text(_), CellKey::Number(_)) => Ordering::Greater,
+        (CellKey::Text(a), CellKey::Text(b)) => a.cmp(b),
+    }
+}
+
+fn escape_csv(cell: &str) -> String {
+    if cell.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
@@ -0,0 +1,22 @@
+use crate::base64;
+
+#[test]
+fn encodes_with_padding() {
+    assert_eq!("", base64::encode(b""));
+    assert_eq!("Zg==", base64::encode(b"f"));
+    assert_eq!("Zm8=", base64::encode(b"fo"));
+    assert_eq!("Zm9v", base64::encode(b"foo"));
+    assert_eq!("Zm9vYmFy", base64::encode(b"foobar"));
+}
+
+#[test]
+fn decodes_standard_and_url_safe_alphabets() {
+    assert_eq!(Some(b"foobar".to_vec()), base64::decode("Zm9vYmFy"));
+    assert_eq!(Some(b"fo".to_vec()), base64::decode("Zm8="));
+    assert_eq!(Some(b"fo".to_vec()), base64::decode("Zm8"));
+    assert_eq!(Some(vec![0xfb, 0xff]), base64::decode("-_8"));
+    assert_eq!(Some(vec![0xfb, 0xff]), base64::decode("+/8=\n"));
+    assert_eq!(None, base64::decode("Zm9vY"));
+    assert_eq!(None, base64::decode("Zm=9"));
+    assert_eq!(None, base64::decode("Zm9v!"));
+}
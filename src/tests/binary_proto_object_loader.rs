@@ -4,8 +4,7 @@ use crate::assert_variant;
 fn parses_numbers() {
     let source = "0, 1,  254,255 ";
 
-    let actual =
-        crate::binary_proto_object_loader::parse_u8_vec_from_string_with_integers(source).unwrap();
+    let actual = crate::binary_proto_object_loader::parse_u8_vec_from_string(source).unwrap();
 
     assert_eq!(actual[0], 0_u8);
     assert_eq!(actual[1], 1_u8);
@@ -20,8 +19,7 @@ fn parses_numbers_from_multiline_string() {
                       254,
                       255 ";
 
-    let actual =
-        crate::binary_proto_object_loader::parse_u8_vec_from_string_with_integers(source).unwrap();
+    let actual = crate::binary_proto_object_loader::parse_u8_vec_from_string(source).unwrap();
 
     assert_eq!(actual[0], 0_u8);
     assert_eq!(actual[1], 1_u8);
@@ -36,8 +34,7 @@ fn parses_numbers_from_tab_separeted_string() {
                       254\t,
                       255 \t\t";
 
-    let actual =
-        crate::binary_proto_object_loader::parse_u8_vec_from_string_with_integers(source).unwrap();
+    let actual = crate::binary_proto_object_loader::parse_u8_vec_from_string(source).unwrap();
 
     assert_eq!(actual[0], 0_u8);
     assert_eq!(actual[1], 1_u8);
@@ -47,25 +44,14 @@ fn parses_numbers_from_tab_separeted_string() {
 
 #[test]
 fn does_not_parses_invalid_values() {
+    assert!(crate::binary_proto_object_loader::parse_u8_vec_from_string("0, 1,  0f,0a ").is_err());
     assert!(
-        crate::binary_proto_object_loader::parse_u8_vec_from_string_with_integers("0, 1,  0f,0a ")
-            .is_err()
+        crate::binary_proto_object_loader::parse_u8_vec_from_string("0, 1,  254, 256 ").is_err()
     );
     assert!(
-        crate::binary_proto_object_loader::parse_u8_vec_from_string_with_integers(
-            "0, 1,  254, 256 "
-        )
-        .is_err()
-    );
-    assert!(
-        crate::binary_proto_object_loader::parse_u8_vec_from_string_with_integers(
-            "0 1,  254, 256 "
-        )
-        .is_err()
-    );
-    assert!(
-        crate::binary_proto_object_loader::parse_u8_vec_from_string_with_integers("-1, 1").is_err()
+        crate::binary_proto_object_loader::parse_u8_vec_from_string("0 1,  254, 256 ").is_err()
     );
+    assert!(crate::binary_proto_object_loader::parse_u8_vec_from_string("-1, 1").is_err());
 }
 
 #[test]
@@ -80,7 +66,221 @@ fn parses_real_object() {
             0, 73, 9, 0, 0, 0, 0, 0, 0, 0, 85, 10, 0, 0, 0, 89, 11, 0, 0, 0, 0, 0, 0, 0,
         ";
 
-    let actual = crate::binary_proto_object_loader::parse_u8_vec_from_string_with_integers(string);
+    let actual = crate::binary_proto_object_loader::parse_u8_vec_from_string(string);
 
     assert_variant!(actual, Ok(_));
 }
+
+#[test]
+fn parses_hex_in_common_shapes() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    let expected = vec![0x0a, 0x08, 0xff, 0x01];
+
+    assert_eq!(expected, parse_u8_vec_from_string("0a08FF01").unwrap());
+    assert_eq!(expected, parse_u8_vec_from_string("0a 08 ff\n01").unwrap());
+    assert_eq!(expected, parse_u8_vec_from_string("0A:08:Ff:01").unwrap());
+    assert_eq!(
+        expected,
+        parse_u8_vec_from_string("0x0a 0x8 0xFF 0x01").unwrap()
+    );
+    assert_eq!(
+        expected,
+        parse_u8_vec_from_string("0x0a, 0x08, 0xff, 0x01").unwrap()
+    );
+    assert_eq!(
+        vec![10, 8, 255, 1],
+        parse_u8_vec_from_string("10, 8, 255, 1").unwrap()
+    );
+}
+
+#[test]
+fn reports_position_of_invalid_hex() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    assert_eq!(
+        "Unexpected character 'g' at position 5",
+        parse_u8_vec_from_string("0a 0g1").unwrap_err().message
+    );
+    assert_eq!(
+        "Odd number of hex digits in 0a0 at position 4",
+        parse_u8_vec_from_string("ff 0a0").unwrap_err().message
+    );
+    assert_eq!(
+        "0x100 is not a single byte at position 1",
+        parse_u8_vec_from_string("0x100").unwrap_err().message
+    );
+    assert_eq!(
+        "The input is empty",
+        parse_u8_vec_from_string(" \n").unwrap_err().message
+    );
+}
+
+#[test]
+fn parses_base64_and_base64url() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    assert_eq!(
+        vec![0x08, 0x96, 0x01],
+        parse_u8_vec_from_string("CJYB").unwrap()
+    );
+    assert_eq!(
+        vec![0xfb, 0xff, 0x0a],
+        parse_u8_vec_from_string("+/8K").unwrap()
+    );
+    assert_eq!(
+        vec![0xfb, 0xff, 0x0a],
+        parse_u8_vec_from_string("-_8K").unwrap()
+    );
+    assert_eq!(
+        b"protobuf".to_vec(),
+        parse_u8_vec_from_string("cHJvdG9i\r\n  dWY=\n").unwrap()
+    );
+    assert_eq!(vec![0xfb, 0xff], parse_u8_vec_from_string("-_8").unwrap());
+    assert_eq!(
+        b"protobuf".to_vec(),
+        parse_u8_vec_from_string("cHJvdG9idWY").unwrap()
+    );
+    assert_eq!(
+        vec![0x0a, 0x04, b'J', b'o', b'h', b'n', 0x10, 0x01],
+        parse_u8_vec_from_string("CgRKb2huEAE").unwrap()
+    );
+}
+
+#[test]
+fn reads_text_valid_as_hex_and_base64_as_hex() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    assert_eq!(
+        vec![0xde, 0xad, 0xbe, 0xef],
+        parse_u8_vec_from_string("deadbeef").unwrap()
+    );
+    assert_eq!(
+        vec![0x0a, 0x08, 0x96, 0x01],
+        parse_u8_vec_from_string("0a08 9601").unwrap()
+    );
+}
+
+#[test]
+fn reports_invalid_base64() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    assert_eq!(
+        "Unexpected character 'Y' after padding at position 4",
+        parse_u8_vec_from_string("Zg=Y").unwrap_err().message
+    );
+    assert_eq!(
+        "Too much padding at position 3",
+        parse_u8_vec_from_string("Zg===").unwrap_err().message
+    );
+    assert_eq!(
+        "The base64 input is one character too long or too short",
+        parse_u8_vec_from_string("CJYB/").unwrap_err().message
+    );
+}
+
+#[test]
+fn parses_escaped_string_literals() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    let john = vec![0x0a, 0x04, b'J', b'o', b'h', b'n'];
+
+    assert_eq!(john, parse_u8_vec_from_string(r"b'\x0a\x04John'").unwrap());
+    assert_eq!(
+        john,
+        parse_u8_vec_from_string("data = \"\\012\\4Jo\"\n       \"hn\";").unwrap()
+    );
+    assert_eq!(
+        vec![b'\'', b'"', 0xc3, 0xa9, 0xc3, 0xa9, b'\n'],
+        parse_u8_vec_from_string(r#"b"'\"é\u{e9}\n""#).unwrap()
+    );
+}
+
+#[test]
+fn parses_array_literals_with_signed_bytes() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    assert_eq!(
+        vec![0x0a, 0x04, 0x4a],
+        parse_u8_vec_from_string("[]byte{0x0a, 0x04, 0x4A}").unwrap()
+    );
+    assert_eq!(
+        vec![10, 0x80, 0xff, 0xff],
+        parse_u8_vec_from_string("byte[] data = new byte[]{10, -128, (byte)0xff, -1};").unwrap()
+    );
+    assert_eq!(
+        vec![10, 1, 15],
+        parse_u8_vec_from_string("let data: &[u8] = &[10u8, 0b1, 0o17,];").unwrap()
+    );
+    assert_eq!(
+        vec![10, 4],
+        parse_u8_vec_from_string("bytes([10, 4])").unwrap()
+    );
+}
+
+#[test]
+fn reports_invalid_literals() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    assert_eq!(
+        r"Missing hex digits in \x at position 3",
+        parse_u8_vec_from_string(r"b'\xg'").unwrap_err().message
+    );
+    assert_eq!(
+        r"Octal escape \400 is not a single byte at position 2",
+        parse_u8_vec_from_string(r#""\400""#).unwrap_err().message
+    );
+    assert_eq!(
+        "Unterminated string at position 2",
+        parse_u8_vec_from_string("b'ab").unwrap_err().message
+    );
+    assert_eq!(
+        "300 is not a byte at position 6",
+        parse_u8_vec_from_string("{10, 300}").unwrap_err().message
+    );
+    assert_eq!(
+        "-129 is not a byte at position 2",
+        parse_u8_vec_from_string("[-129]").unwrap_err().message
+    );
+}
+
+#[test]
+fn parses_single_decimal_byte() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    assert_eq!(vec![8], parse_u8_vec_from_string("8").unwrap());
+    assert_eq!(vec![150], parse_u8_vec_from_string(" 150\n").unwrap());
+    assert_eq!(vec![1, 2], parse_u8_vec_from_string("1, 2,").unwrap());
+}
+
+#[test]
+fn reports_position_of_invalid_decimal() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    assert_eq!(
+        "x is not a decimal byte at position 5",
+        parse_u8_vec_from_string("1,2,x").unwrap_err().message
+    );
+    assert_eq!(
+        "256 is not a decimal byte at position 5",
+        parse_u8_vec_from_string("0,  256, 1").unwrap_err().message
+    );
+    assert_eq!(
+        "Missing byte at position 3",
+        parse_u8_vec_from_string("1,,2").unwrap_err().message
+    );
+}
+
+#[test]
+fn reports_hex_error_when_base64_fails_too() {
+    use crate::binary_proto_object_loader::parse_u8_vec_from_string;
+
+    assert_eq!(
+        "Unexpected character 'z' at position 5",
+        parse_u8_vec_from_string("0a08z").unwrap_err().message
+    );
+    assert_eq!(
+        "Unexpected character 'g' at position 8",
+        parse_u8_vec_from_string("0a08 96g1 0").unwrap_err().message
+    );
+}
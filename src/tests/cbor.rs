@@ -0,0 +1,40 @@
+use crate::cbor;
+use crate::document::DocumentValue;
+
+#[test]
+fn decodes_nested_document() {
+    // {"a": 1, "b": [2, -3, h'ff'], "c": 1.5, "d": 0("2013-03-21")}
+    let buffer = b"\xa4\x61a\x01\x61b\x83\x02\x22\x41\xff\x61c\xf9\x3e\x00\x61d\xc0\x6a2013-03-21";
+
+    assert_eq!(
+        r#"{"a": 1, "b": [2, -3, h'ff'], "c": 1.5, "d": 0("2013-03-21")}"#,
+        cbor::decode(buffer).unwrap().format()
+    );
+}
+
+#[test]
+fn decodes_indefinite_lengths() {
+    assert_eq!(
+        DocumentValue::Array(vec![
+            DocumentValue::Text("ab".to_string()),
+            DocumentValue::Bool(true),
+            DocumentValue::Null,
+        ]),
+        cbor::decode(b"\x9f\x7f\x61a\x61b\xff\xf5\xf6\xff").unwrap()
+    );
+}
+
+#[test]
+fn reports_invalid_data_with_offset() {
+    assert_eq!(
+        "Length 2 exceeds the data at byte 1",
+        cbor::decode(b"\x62a").unwrap_err().message
+    );
+    assert_eq!(
+        "Unexpected data after the value at byte 1",
+        cbor::decode(b"\x01\x02").unwrap_err().message
+    );
+    assert!(cbor::decode(b"\xff").is_err());
+    assert!(cbor::decode(b"\x9b\xff\xff\xff\xff\xff\xff\xff\xff").is_err());
+    assert!(cbor::decode(&[0x81; 100]).is_err());
+}
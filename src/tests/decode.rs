@@ -201,3 +201,21 @@ fn parses_small_object_for_example() {
     // println!("{:?}", buffer);
     crate::decode::decode_message(&buffer).unwrap();
 }
+
+#[test]
+fn does_not_parse_truncated_buffers() {
+    assert!(crate::decode::decode_message(&[10, 5, 1, 2]).is_err());
+    assert!(crate::decode::decode_message(&[8]).is_err());
+    assert!(crate::decode::decode_message(&[18]).is_err());
+}
+
+#[test]
+fn does_not_parse_malformed_varints() {
+    assert!(crate::decode::decode_message(&[8, 0x96]).is_err());
+    assert!(crate::decode::decode_message(&[0x88, 0x80]).is_err());
+
+    let mut overlong = vec![8];
+    overlong.extend_from_slice(&[0xff; 19]);
+    overlong.push(0x01);
+    assert!(crate::decode::decode_message(&overlong).is_err());
+}
@@ -0,0 +1,141 @@
+use crate::assert_variant;
+use crate::data::Data;
+use crate::dictionary::*;
+use crate::kind_varint::KindVarint;
+use crate::model::DecodingState;
+use crate::proto_type::ProtoType;
+use crate::tests::test_data::*;
+use protobuf::Message;
+
+#[test]
+fn parses_shorthand_entries() {
+    let dictionary =
+        FieldDictionary::parse(r#"{ "4.1": "phones.number: string", "2": "id" }"#).unwrap();
+
+    let entry = dictionary.get(&[4, 1]).unwrap();
+    assert_eq!(Some("phones.number".to_string()), entry.name);
+    assert_eq!(Some(ProtoType::String), entry.proto_type);
+
+    let entry = dictionary.get(&[2]).unwrap();
+    assert_eq!(Some("id".to_string()), entry.name);
+    assert_eq!(None, entry.proto_type);
+}
+
+#[test]
+fn parses_object_entries_with_enum_labels() {
+    let dictionary = FieldDictionary::parse(
+        r#"{ "4.2": { "name": "type", "type": "enum", "enum": { "0": "MOBILE", "2": "WORK" } } }"#,
+    )
+    .unwrap();
+
+    let entry = dictionary.get(&[4, 2]).unwrap();
+    assert_eq!(Some(ProtoType::Enum), entry.proto_type);
+    assert_eq!(Some("WORK"), entry.get_enum_label(2));
+    assert_eq!(None, entry.get_enum_label(1));
+}
+
+#[test]
+fn does_not_parse_invalid_dictionaries() {
+    assert!(FieldDictionary::parse("[]").is_err());
+    assert!(FieldDictionary::parse(r#"{ "4.x": "name" }"#).is_err());
+    assert!(FieldDictionary::parse(r#"{ "4": "name: text" }"#).is_err());
+    assert!(FieldDictionary::parse(r#"{ "4": 1 }"#).is_err());
+}
+
+#[test]
+fn formats_and_parses_field_path() {
+    assert_eq!("1.4.2", format_field_path(&[1, 4, 2]));
+    assert_eq!(vec![1, 4, 2], parse_field_path("1.4.2").unwrap());
+}
+
+#[test]
+fn applies_declared_types_to_decoded_tree() {
+    let mut phone_number = Person_PhoneNumber::new();
+    phone_number.set_number("117".into());
+    phone_number.set_field_type(Person_PhoneType::HOME);
+    let mut person = Person::new();
+    person.phones.push(phone_number);
+    let buffer = person.write_to_bytes().unwrap();
+
+    let mut state: DecodingState = Data::Message {
+        field_number: 0,
+        items: crate::decode::decode_message(&buffer).unwrap(),
+    }
+    .into();
+
+    let dictionary = FieldDictionary::parse(
+        r#"{ "4": "phones: message", "4.1": "number: string", "4.2": "type: sint32" }"#,
+    )
+    .unwrap();
+    state.apply_dictionary(&dictionary, &[]);
+
+    if let DecodingState::Message { items, .. } = &state {
+        if let DecodingState::Message { items, .. } = &items[0] {
+            assert_variant!(items[0], DecodingState::Utf8String { .. });
+            assert_variant!(
+                items[1],
+                DecodingState::Varint {
+                    kind: KindVarint::ZigZag,
+                    ..
+                }
+            );
+        } else {
+            panic!()
+        }
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn exported_json_can_be_parsed_back() {
+    let mut dictionary = FieldDictionary::parse(
+        r#"{ "4.2": { "name": "type", "type": "enum", "enum": { "2": "WORK" }, "note": "Phone kind" } }"#,
+    )
+    .unwrap();
+    dictionary.set(
+        vec![1],
+        FieldEntry {
+            name: Some("name".into()),
+            ..Default::default()
+        },
+    );
+
+    let actual = FieldDictionary::parse(&dictionary.to_json()).unwrap();
+
+    assert_eq!(dictionary.entries, actual.entries);
+}
+
+#[test]
+fn removes_entries_set_to_empty() {
+    let mut dictionary = FieldDictionary::parse(r#"{ "1": "name" }"#).unwrap();
+
+    dictionary.set(vec![1], FieldEntry::default());
+
+    assert!(dictionary.get(&[1]).is_none());
+}
+
+#[test]
+fn formats_and_parses_enum_labels() {
+    let enum_labels = parse_enum_labels(" 0 = MOBILE, 2=WORK, ").unwrap();
+
+    assert_eq!(Some(&"MOBILE".to_string()), enum_labels.get(&0));
+    assert_eq!("0=MOBILE, 2=WORK", format_labels(&enum_labels));
+    assert!(parse_enum_labels("WORK").is_err());
+    assert!(parse_enum_labels("2=").is_err());
+}
+
+#[test]
+fn parses_flag_bit_names() {
+    let dictionary = FieldDictionary::parse(
+        r#"{ "3": { "name": "mode", "flags": { "0": "READ", "1": "WRITE" } } }"#,
+    )
+    .unwrap();
+
+    let entry = dictionary.get(&[3]).unwrap();
+
+    assert_eq!(Some(&"WRITE".to_string()), entry.bit_names.get(&1));
+    assert_eq!("0=READ, 1=WRITE", format_labels(&entry.bit_names));
+    assert_eq!(entry.bit_names, parse_bit_names("0=READ, 1=WRITE").unwrap());
+    assert!(parse_bit_names("-1=READ").is_err());
+}
@@ -0,0 +1,16 @@
+use crate::document::DocumentValue;
+
+#[test]
+fn formats_values_in_one_line() {
+    let value = DocumentValue::Map(vec![(
+        DocumentValue::Integer(1),
+        DocumentValue::Array(vec![
+            DocumentValue::Bytes(vec![0x01, 0xab]),
+            DocumentValue::Float(1.0),
+            DocumentValue::Undefined,
+        ]),
+    )]);
+
+    assert_eq!("{1: [h'01ab', 1.0, undefined]}", value.format());
+    assert_eq!("map 1", value.get_type_name());
+}
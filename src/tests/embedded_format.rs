@@ -0,0 +1,72 @@
+use crate::embedded_format::*;
+
+#[test]
+fn detects_embedded_formats() {
+    assert_eq!(
+        Some(EmbeddedFormat::Json),
+        EmbeddedFormat::detect(r#" {"a": [1, 2]} "#)
+    );
+    assert_eq!(
+        Some(EmbeddedFormat::Xml),
+        EmbeddedFormat::detect("<a><b>1</b></a>")
+    );
+    assert_eq!(
+        Some(EmbeddedFormat::UrlEncoded),
+        EmbeddedFormat::detect("q=hello+world&page=2")
+    );
+    assert_eq!(None, EmbeddedFormat::detect("{not json"));
+    assert_eq!(None, EmbeddedFormat::detect("<a><b></a>"));
+    assert_eq!(None, EmbeddedFormat::detect("just text"));
+    assert_eq!(None, EmbeddedFormat::detect("1 = 2"));
+}
+
+#[test]
+fn does_not_detect_padded_base64_as_form() {
+    assert_eq!(None, EmbeddedFormat::detect("aGVsbG8="));
+    assert_eq!(None, EmbeddedFormat::detect("aGk="));
+    assert_eq!(None, EmbeddedFormat::detect("aA=="));
+    assert_eq!(None, parse_url_encoded("aA=="));
+    assert_eq!(None, parse_url_encoded("a=1&flag"));
+    assert_eq!(
+        Some(EmbeddedFormat::UrlEncoded),
+        EmbeddedFormat::detect("token=aGVsbG8=")
+    );
+}
+
+#[test]
+fn indents_xml() {
+    let xml = r#"<?xml version="1.0"?><root id="1"><name>Ann</name><empty/><!-- c --><list><item>a</item></list></root>"#;
+
+    assert_eq!(
+        Some(
+            [
+                r#"<?xml version="1.0"?>"#,
+                r#"<root id="1">"#,
+                "  <name>Ann</name>",
+                "  <empty/>",
+                "  <!-- c -->",
+                "  <list>",
+                "    <item>a</item>",
+                "  </list>",
+                "</root>",
+            ]
+            .join("\n")
+        ),
+        format_xml(xml)
+    );
+}
+
+#[test]
+fn decodes_form_parameters() {
+    assert_eq!(
+        Some(vec![
+            ("q".to_string(), "a b&c".to_string()),
+            ("empty".to_string(), String::new()),
+        ]),
+        parse_url_encoded("q=a+b%26c&empty=")
+    );
+    assert_eq!(None, parse_url_encoded("q=%zz"));
+    assert_eq!(None, parse_url_encoded("=value"));
+    assert_eq!(None, parse_url_encoded("q=%+1"));
+    assert_eq!(None, parse_url_encoded("q=%-1"));
+}
@@ -0,0 +1,31 @@
+use crate::entropy;
+
+#[test]
+fn computes_shannon_entropy() {
+    assert_eq!(0.0, entropy::get_entropy(b""));
+    assert_eq!(0.0, entropy::get_entropy(b"aaaa"));
+    assert_eq!(2.0, entropy::get_entropy(b"abcd"));
+
+    let all_bytes: Vec<u8> = (0..=255).collect();
+    assert_eq!(8.0, entropy::get_entropy(&all_bytes));
+}
+
+#[test]
+fn flags_only_long_evenly_distributed_chunks() {
+    let all_bytes: Vec<u8> = (0..=255).collect();
+    let text = b"The quick brown fox jumps over the lazy dog. ".repeat(4);
+
+    assert!(entropy::is_high_entropy(&all_bytes));
+    assert!(!entropy::is_high_entropy(&all_bytes[..32]));
+    assert!(!entropy::is_high_entropy(&text));
+}
+
+#[test]
+fn groups_byte_counts_into_buckets() {
+    let mut expected = vec![0; entropy::HISTOGRAM_BUCKET_COUNT];
+    expected[0] = 2;
+    expected[3] = 1;
+    expected[15] = 1;
+
+    assert_eq!(expected, entropy::get_histogram(&[0x00, 0x0f, 0x3f, 0xff]));
+}
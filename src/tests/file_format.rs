@@ -0,0 +1,27 @@
+use crate::file_format::FileFormat;
+
+#[test]
+fn detects_formats_by_magic_bytes() {
+    assert_eq!(
+        Some(FileFormat::Png),
+        FileFormat::detect(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")
+    );
+    assert_eq!(
+        Some(FileFormat::WebP),
+        FileFormat::detect(b"RIFF\x24\0\0\0WEBPVP8 ")
+    );
+    assert_eq!(None, FileFormat::detect(b"RIFF\x24\0\0\0WAVEfmt "));
+    assert_eq!(Some(FileFormat::Pdf), FileFormat::detect(b"%PDF-1.7\n"));
+    assert_eq!(None, FileFormat::detect(b"\x08\x96\x01"));
+    assert_eq!(None, FileFormat::detect(b""));
+}
+
+#[test]
+fn builds_data_url_for_download() {
+    assert!(FileFormat::Gif.is_image());
+    assert!(!FileFormat::Zip.is_image());
+    assert_eq!(
+        "data:image/gif;base64,R0lGODlh",
+        FileFormat::Gif.get_data_url(b"GIF89a")
+    );
+}
@@ -0,0 +1,63 @@
+use crate::float_format::FloatFormat;
+
+#[test]
+fn expands_floats_exactly() {
+    assert_eq!(
+        Some("0.100000001490116119384765625".to_string()),
+        FloatFormat::Exact.format(0.1f32.to_bits() as u64, 32)
+    );
+    assert_eq!(
+        Some("-2.5".to_string()),
+        FloatFormat::Exact.format((-2.5f64).to_bits(), 64)
+    );
+    assert_eq!(
+        Some("16777216".to_string()),
+        FloatFormat::Exact.format(16_777_216f32.to_bits() as u64, 32)
+    );
+
+    let smallest_subnormal = FloatFormat::Exact.format(1, 32).unwrap();
+    assert!(
+        smallest_subnormal.starts_with("0.00000000000000000000000000000000000000000000140129846")
+    );
+    assert_eq!(
+        Some("NaN (payload 0x400001)".to_string()),
+        FloatFormat::Exact.format(0x7fc0_0001, 32)
+    );
+    assert_eq!(
+        Some("-inf".to_string()),
+        FloatFormat::Exact.format(0xff80_0000, 32)
+    );
+}
+
+#[test]
+fn formats_hex_floats() {
+    assert_eq!(
+        Some("0x1.921fb6p+1".to_string()),
+        FloatFormat::Hex.format(std::f32::consts::PI.to_bits() as u64, 32)
+    );
+    assert_eq!(
+        Some("-0x1p+0".to_string()),
+        FloatFormat::Hex.format((-1f64).to_bits(), 64)
+    );
+    assert_eq!(
+        Some("0x0.000002p-126".to_string()),
+        FloatFormat::Hex.format(1, 32)
+    );
+    assert_eq!(Some("0x0p+0".to_string()), FloatFormat::Hex.format(0, 64));
+}
+
+#[test]
+fn breaks_float_into_parts() {
+    assert_eq!(
+        Some(
+            "sign 1 | exponent 10000000 (+1) | mantissa 01000000000000000000000 | normal"
+                .to_string()
+        ),
+        FloatFormat::Parts.format((-2.5f32).to_bits() as u64, 32)
+    );
+    assert!(FloatFormat::Parts
+        .format(0x7fa0_0000, 32)
+        .unwrap()
+        .ends_with("signaling NaN"));
+    assert_eq!(None, FloatFormat::Shortest.format(0, 32));
+}
@@ -1 +1,7 @@
+use crate::format::*;
 
+#[test]
+fn formats_bytes_as_hex() {
+    assert_eq!("0a ff 00", format_as_hex(&[10, 255, 0]));
+    assert_eq!("", format_as_hex(&[]));
+}
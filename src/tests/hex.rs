@@ -0,0 +1,26 @@
+use crate::hex;
+
+#[test]
+fn encodes_lowercase_digits() {
+    assert_eq!("", hex::encode(b""));
+    assert_eq!("0aff10", hex::encode(&[0x0a, 0xff, 0x10]));
+    assert_eq!("0a:ff:10", hex::encode_separated(&[0x0a, 0xff, 0x10], ":"));
+}
+
+#[test]
+fn decodes_digits_in_any_case_with_whitespace() {
+    assert_eq!(Some(vec![0x0a, 0xff, 0x10]), hex::decode("0aFf10"));
+    assert_eq!(Some(vec![0x0a, 0xff]), hex::decode("0a ff\n"));
+    assert_eq!(Some(vec![]), hex::decode(""));
+    assert_eq!(None, hex::decode("0af"));
+    assert_eq!(None, hex::decode("0g"));
+}
+
+#[test]
+fn decodes_single_bytes() {
+    assert_eq!(Some(0x05), hex::decode_byte("5"));
+    assert_eq!(Some(0xfe), hex::decode_byte("FE"));
+    assert_eq!(None, hex::decode_byte(""));
+    assert_eq!(None, hex::decode_byte("100"));
+    assert_eq!(None, hex::decode_byte("+f"));
+}
@@ -0,0 +1,41 @@
+use crate::integer_format::IntegerFormat;
+use std::collections::BTreeMap;
+
+#[test]
+fn formats_in_every_radix() {
+    let names = BTreeMap::new();
+
+    assert_eq!(None, IntegerFormat::Decimal.format(255, 32, &names));
+    assert_eq!(
+        Some("0xff".to_string()),
+        IntegerFormat::Hex.format(255, 32, &names)
+    );
+    assert_eq!(
+        Some("0o377".to_string()),
+        IntegerFormat::Octal.format(255, 32, &names)
+    );
+    assert_eq!(
+        Some("0b101".to_string()),
+        IntegerFormat::Binary.format(5, 32, &names)
+    );
+}
+
+#[test]
+fn splits_value_into_named_flags() {
+    let mut names = BTreeMap::new();
+    names.insert(0, "READ".to_string());
+    names.insert(2, "EXECUTE".to_string());
+
+    assert_eq!(
+        Some("READ | bit 1 | EXECUTE".to_string()),
+        IntegerFormat::Flags.format(7, 32, &names)
+    );
+    assert_eq!(
+        Some("(no flags)".to_string()),
+        IntegerFormat::Flags.format(0, 32, &names)
+    );
+    assert_eq!(
+        Some("bit 31".to_string()),
+        IntegerFormat::Flags.format(1 << 31, 32, &names)
+    );
+}
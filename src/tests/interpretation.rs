@@ -0,0 +1,44 @@
+use crate::interpretation::Interpretation;
+use crate::kind32::Kind32;
+use crate::kind_message::KindMessage;
+use crate::model::DecodingState;
+use uuid::Uuid;
+
+#[test]
+fn applies_only_to_matching_wire_type() {
+    let mut state = DecodingState::Fixed32 {
+        field_number: 1,
+        buffer: vec![0, 0, 128, 63],
+        uuid: Uuid::new_v4(),
+        kind: Kind32::Fixed32,
+    };
+
+    Interpretation::Fixed32(Kind32::Float).apply(&mut state);
+    assert_eq!(
+        Some(Interpretation::Fixed32(Kind32::Float)),
+        Interpretation::from_state(&state)
+    );
+
+    Interpretation::Message(KindMessage::Message).apply(&mut state);
+    assert!(matches!(state, DecodingState::Fixed32 { .. }));
+}
+
+#[test]
+fn decodes_chunk_as_message_of_remembered_kind() {
+    let mut state = DecodingState::Chunk {
+        field_number: 1,
+        buffer: vec![8, 5],
+        uuid: Uuid::new_v4(),
+    };
+
+    Interpretation::Message(KindMessage::Int64Value).apply(&mut state);
+
+    assert_eq!(
+        Some(Interpretation::Message(KindMessage::Int64Value)),
+        Interpretation::from_state(&state)
+    );
+    assert_eq!(
+        "varint zigzag",
+        Interpretation::Varint(crate::kind_varint::KindVarint::ZigZag).get_description()
+    );
+}
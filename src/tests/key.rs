@@ -8,7 +8,7 @@ fn takes_correct_first_key_for_simple_i32_wrapper() {
     i32_wrapper.set_value(117);
     let buffer = i32_wrapper.write_to_bytes().unwrap();
 
-    let (key, _buffer) = crate::key::take_key(&buffer);
+    let (key, _buffer) = crate::key::take_key(&buffer).unwrap();
 
     assert_eq!(WireType::Varint, key.wire_type);
     assert_eq!(1, key.field_number);
@@ -20,7 +20,7 @@ fn takes_correct_first_key_for_simple_string_wrapper() {
     string_wrapper.set_value("117".into());
     let buffer = string_wrapper.write_to_bytes().unwrap();
 
-    let (key, _buffer) = crate::key::take_key(&buffer);
+    let (key, _buffer) = crate::key::take_key(&buffer).unwrap();
 
     assert_eq!(WireType::LengthDelimited, key.wire_type);
     assert_eq!(1, key.field_number);
@@ -34,12 +34,12 @@ fn takes_correct_keys_for_phone_number() {
 
     let buffer = phone_number.write_to_bytes().unwrap();
 
-    let (key, _buffer) = crate::key::take_key(&buffer);
+    let (key, _buffer) = crate::key::take_key(&buffer).unwrap();
 
     assert_eq!(WireType::LengthDelimited, key.wire_type);
     assert_eq!(1, key.field_number);
 
-    let (key, _buffer) = crate::key::take_key(&buffer[5..]);
+    let (key, _buffer) = crate::key::take_key(&buffer[5..]).unwrap();
 
     assert_eq!(WireType::Varint, key.wire_type);
     assert_eq!(2, key.field_number);
@@ -1,4 +1,5 @@
 use crate::kind32::Kind32;
+use crate::timestamp_unit::TimestampUnit;
 
 #[test]
 fn toggles_itself_in_cycle() {
@@ -8,3 +9,41 @@ fn toggles_itself_in_cycle() {
 
     assert_eq!(actual, kind);
 }
+
+#[test]
+fn renders_and_suggests_timestamp() {
+    let buffer = 1_600_000_000u32.to_le_bytes();
+
+    assert_eq!(
+        Some(TimestampUnit::Seconds),
+        Kind32::suggest_timestamp_unit(&buffer)
+    );
+    assert_eq!(
+        "2020-09-13T12:26:40Z",
+        Kind32::Timestamp(TimestampUnit::Seconds).get_value_as_string(&buffer)
+    );
+    assert_eq!(
+        Kind32::Buffer,
+        Kind32::Timestamp(TimestampUnit::Seconds).toggle()
+    );
+}
+
+#[test]
+fn reads_unsigned_and_signed_values() {
+    let buffer = [0xff, 0xff, 0xff, 0xff];
+
+    assert_eq!("4294967295", Kind32::Fixed32.get_value_as_string(&buffer));
+    assert_eq!("-1", Kind32::SFixed32.get_value_as_string(&buffer));
+    assert_eq!(
+        Some((0xffff_ffff, 32)),
+        Kind32::SFixed32.get_integer_bits(&buffer)
+    );
+}
+
+#[test]
+fn reads_ipv4_address() {
+    assert_eq!(
+        "127.0.0.1",
+        Kind32::Ipv4.get_value_as_string(&0x7f00_0001u32.to_le_bytes())
+    );
+}
@@ -1,4 +1,5 @@
 use crate::kind64::Kind64;
+use crate::timestamp_unit::TimestampUnit;
 
 #[test]
 fn toggles_itself_in_cycle() {
@@ -8,3 +9,48 @@ fn toggles_itself_in_cycle() {
 
     assert_eq!(actual, kind);
 }
+
+#[test]
+fn renders_and_suggests_timestamp_in_milliseconds() {
+    let buffer = 1_600_000_000_123i64.to_le_bytes();
+
+    assert_eq!(
+        Some(TimestampUnit::Milliseconds),
+        Kind64::suggest_timestamp_unit(&buffer)
+    );
+    assert_eq!(
+        "2020-09-13T12:26:40.123Z",
+        Kind64::Timestamp(TimestampUnit::Milliseconds).get_value_as_string(&buffer)
+    );
+}
+
+#[test]
+fn reads_unsigned_and_signed_values() {
+    let buffer = [0xff; 8];
+
+    assert_eq!(
+        "18446744073709551615",
+        Kind64::Fixed64.get_value_as_string(&buffer)
+    );
+    assert_eq!("-1", Kind64::SFixed64.get_value_as_string(&buffer));
+    assert_eq!(
+        Some((u64::MAX, 64)),
+        Kind64::SFixed64.get_integer_bits(&buffer)
+    );
+}
+
+#[test]
+fn reads_big_endian_values() {
+    let buffer = [0, 0, 0, 0, 0, 0, 0x01, 0x02];
+
+    assert_eq!("258", Kind64::Fixed64BigEndian.get_value_as_string(&buffer));
+    assert_eq!(
+        "1.5",
+        Kind64::DoubleBigEndian.get_value_as_string(&1.5f64.to_be_bytes())
+    );
+    assert_eq!(
+        Some((1.5f64.to_bits(), 64)),
+        Kind64::DoubleBigEndian.get_float_bits(&1.5f64.to_be_bytes())
+    );
+    assert_eq!(None, Kind64::Fixed64.get_float_bits(&buffer));
+}
@@ -0,0 +1,53 @@
+use crate::kind_bytes::KindBytes;
+
+#[test]
+fn renders_uuid_and_addresses() {
+    let uuid: Vec<u8> = (0..16).collect();
+
+    assert_eq!(
+        "00010203-0405-0607-0809-0a0b0c0d0e0f",
+        KindBytes::Uuid.get_value_as_string(&uuid)
+    );
+    assert_eq!(
+        "192.168.0.1",
+        KindBytes::IpAddress.get_value_as_string(&[192, 168, 0, 1])
+    );
+    assert_eq!(
+        "2001:db8::1",
+        KindBytes::IpAddress
+            .get_value_as_string(&[0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1])
+    );
+    assert_eq!(
+        "00:1a:2b:3c:4d:5e",
+        KindBytes::MacAddress.get_value_as_string(&[0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e])
+    );
+}
+
+#[test]
+fn renders_hashes_as_hex_and_base64() {
+    assert_eq!("00ff10", KindBytes::Hex.get_value_as_string(&[0, 255, 16]));
+    assert_eq!("AP8Q", KindBytes::Base64.get_value_as_string(&[0, 255, 16]));
+    assert_eq!("AP8=", KindBytes::Base64.get_value_as_string(&[0, 255]));
+    assert_eq!("AA==", KindBytes::Base64.get_value_as_string(&[0]));
+}
+
+#[test]
+fn rejects_buffers_of_wrong_length() {
+    assert!(!KindBytes::Uuid.fits(&[0; 15]));
+    assert!(!KindBytes::IpAddress.fits(&[0; 5]));
+    assert!(KindBytes::MacAddress.fits(&[0; 8]));
+    assert_eq!(
+        "01 02 (not a uuid value)",
+        KindBytes::Uuid.get_value_as_string(&[1, 2])
+    );
+}
+
+#[test]
+fn renders_text_encodings() {
+    assert_eq!(
+        "a\\xffb",
+        KindBytes::Utf8Lossy.get_value_as_string(b"a\xffb")
+    );
+    assert!(!KindBytes::Utf16Le.fits(&[0, 1, 2]));
+    assert_eq!(Some(KindBytes::Latin1), KindBytes::parse("latin-1"));
+}
@@ -0,0 +1,167 @@
+use crate::data::Data;
+use crate::kind_message::*;
+use crate::model::DecodingState;
+use protobuf::well_known_types::{Duration, ListValue, Struct, Timestamp, Value};
+use protobuf::Message;
+
+fn decode_items(buffer: &[u8]) -> Vec<DecodingState> {
+    crate::decode::decode_message(buffer)
+        .unwrap()
+        .into_iter()
+        .map(DecodingState::from)
+        .collect()
+}
+
+#[test]
+fn formats_timestamp_as_rfc_3339() {
+    assert_eq!("1970-01-01T00:00:00Z", format_timestamp(0, 0));
+    assert_eq!(
+        "2020-02-29T23:59:59.500Z",
+        format_timestamp(1_583_020_799, 500_000_000)
+    );
+    assert_eq!("1969-12-31T23:59:59.000001Z", format_timestamp(-1, 1000));
+    assert_eq!("0001-01-01T00:00:00Z", format_timestamp(-62_135_596_800, 0));
+    assert_eq!(
+        "9999-12-31T23:59:59.000000001Z",
+        format_timestamp(253_402_300_799, 1)
+    );
+}
+
+#[test]
+fn formats_duration_in_human_units() {
+    assert_eq!("0s", format_duration(0, 0));
+    assert_eq!("1h 0m 5.250s", format_duration(3605, 250_000_000));
+    assert_eq!("2d 0h 0m 0s", format_duration(172_800, 0));
+    assert_eq!("-1m 30s", format_duration(-90, 0));
+    assert_eq!("-0.500s", format_duration(0, -500_000_000));
+}
+
+#[test]
+fn suggests_and_renders_timestamp() {
+    let mut timestamp = Timestamp::new();
+    timestamp.set_seconds(1_600_000_000);
+    timestamp.set_nanos(123_000_000);
+    let items = decode_items(&timestamp.write_to_bytes().unwrap());
+
+    assert_eq!(Some(KindMessage::Timestamp), KindMessage::suggest(&items));
+    assert_eq!(
+        Some("2020-09-13T12:26:40.123Z".to_string()),
+        KindMessage::Timestamp.get_value_as_string(&items)
+    );
+}
+
+#[test]
+fn renders_negative_duration() {
+    let mut duration = Duration::new();
+    duration.set_seconds(-5);
+    duration.set_nanos(-100_000_000);
+    let items = decode_items(&duration.write_to_bytes().unwrap());
+
+    assert_eq!(Some(KindMessage::Duration), KindMessage::suggest(&items));
+    assert_eq!(
+        Some("-5.100s".to_string()),
+        KindMessage::Duration.get_value_as_string(&items)
+    );
+}
+
+#[test]
+fn renders_struct_as_json() {
+    let mut number = Value::new();
+    number.set_number_value(1.5);
+    let mut text = Value::new();
+    text.set_string_value("a".into());
+    let mut list = ListValue::new();
+    list.values.push(number.clone());
+    list.values.push(text);
+    let mut list_value = Value::new();
+    list_value.set_list_value(list);
+
+    let mut object = Struct::new();
+    object.fields.insert("number".into(), number);
+    object.fields.insert("list".into(), list_value);
+    let items = decode_items(&object.write_to_bytes().unwrap());
+
+    let actual: serde_json::Value =
+        serde_json::from_str(&KindMessage::Struct.get_value_as_string(&items).unwrap()).unwrap();
+
+    assert_eq!(
+        serde_json::json!({ "number": 1.5, "list": [1.5, "a"] }),
+        actual
+    );
+    assert_eq!(Some(KindMessage::Struct), KindMessage::suggest(&items));
+}
+
+#[test]
+fn unwraps_wrapper_values() {
+    let items = vec![DecodingState::from(Data::Varint {
+        field_number: 1,
+        value: u64::MAX as u128,
+    })];
+
+    assert_eq!(Some(KindMessage::Int64Value), KindMessage::suggest(&items));
+    assert_eq!(
+        Some("-1".to_string()),
+        KindMessage::Int64Value.get_value_as_string(&items)
+    );
+    assert_eq!(
+        Some("true".to_string()),
+        KindMessage::BoolValue.get_value_as_string(&items)
+    );
+    assert_eq!(None, KindMessage::StringValue.get_value_as_string(&items));
+    assert_eq!(
+        Some("0".to_string()),
+        KindMessage::UInt32Value.get_value_as_string(&[])
+    );
+}
+
+#[test]
+fn parses_well_known_type_names() {
+    assert_eq!(
+        Some(KindMessage::Timestamp),
+        KindMessage::from_type_name(".google.protobuf.Timestamp")
+    );
+    assert_eq!(None, KindMessage::from_type_name("protodec.Person"));
+    assert_eq!(None, KindMessage::from_type_name("google.protobuf.message"));
+}
+
+#[test]
+fn detects_any_type_url() {
+    let mut any = protobuf::well_known_types::Any::new();
+    any.set_type_url("type.googleapis.com/protodec.Person".into());
+    any.set_value(vec![10, 1, 65]);
+    let items = decode_items(&any.write_to_bytes().unwrap());
+
+    let type_url = get_any_type_url(&items).unwrap();
+
+    assert_eq!("protodec.Person", get_any_type_name(&type_url));
+    assert_eq!(Some(KindMessage::Any), KindMessage::suggest(&items));
+    assert_eq!(
+        None,
+        get_any_type_url(&decode_items(&[10, 4, 110, 97, 109, 101]))
+    );
+}
+
+#[test]
+fn reads_any_type_url_shown_as_bytes() {
+    let mut any = protobuf::well_known_types::Any::new();
+    any.set_type_url("type.googleapis.com/protodec.Person".into());
+    any.set_value(vec![10, 1, 65]);
+    let mut items = decode_items(&any.write_to_bytes().unwrap());
+    let uuid = items[0].get_uuid();
+    items[0].decode_as_bytes(uuid, crate::kind_bytes::KindBytes::Hex);
+
+    assert_eq!(
+        Some("type.googleapis.com/protodec.Person".to_string()),
+        get_any_type_url(&items)
+    );
+}
+
+#[test]
+fn suggests_bytes_value_for_random_bytes() {
+    let items = vec![DecodingState::from(Data::Chunk {
+        field_number: 1,
+        buffer: (0..=255).collect(),
+    })];
+
+    assert_eq!(Some(KindMessage::BytesValue), KindMessage::suggest(&items));
+}
@@ -1,4 +1,5 @@
 use crate::kind_varint::KindVarint;
+use std::collections::BTreeMap;
 
 #[test]
 fn next_works_in_cycle() {
@@ -24,3 +25,82 @@ fn decodes_zigzag_correctly_64() {
         KindVarint::ZigZag.get_value_as_string(4294967295)
     );
 }
+
+#[test]
+fn formats_enum_value_with_label() {
+    let mut labels = BTreeMap::new();
+    labels.insert(2, "WORK".to_string());
+    labels.insert(-1, "INVALID".to_string());
+
+    assert_eq!("WORK (2)", KindVarint::get_value_as_enum_label(2, &labels));
+    assert_eq!(
+        "INVALID (-1)",
+        KindVarint::get_value_as_enum_label(u64::MAX as u128, &labels)
+    );
+    assert_eq!(
+        "7 (unknown enum value)",
+        KindVarint::get_value_as_enum_label(7, &labels)
+    );
+}
+
+#[test]
+fn enum_toggles_back_to_unsigned() {
+    assert_eq!(KindVarint::Unsigned, KindVarint::Enum.toggle());
+}
+
+#[test]
+fn decodes_sign_extended_negatives() {
+    let minus_one = u64::MAX as u128;
+
+    assert_eq!("-1", KindVarint::Int32.get_value_as_string(minus_one));
+    assert_eq!("-1", KindVarint::Int64.get_value_as_string(minus_one));
+    assert_eq!("true", KindVarint::Bool.get_value_as_string(1));
+    assert_eq!("2", KindVarint::Bool.get_value_as_string(2));
+}
+
+#[test]
+fn checks_ranges() {
+    assert!(KindVarint::Int32.is_in_range(u64::MAX as u128));
+    assert!(KindVarint::Int32.is_in_range(i32::MAX as u128));
+    assert!(!KindVarint::Int32.is_in_range(i32::MAX as u128 + 1));
+    assert!(!KindVarint::Int32.is_in_range(i64::MIN as u64 as u128));
+    assert!(KindVarint::Uint32.is_in_range(u32::MAX as u128));
+    assert!(!KindVarint::Uint32.is_in_range(u32::MAX as u128 + 1));
+    assert!(!KindVarint::Bool.is_in_range(2));
+    assert!(!KindVarint::Unsigned.is_in_range(u64::MAX as u128 + 1));
+}
+
+#[test]
+fn parses_every_kind_back() {
+    for kind in KindVarint::all() {
+        assert_eq!(Some(*kind), KindVarint::parse(kind.to_string()));
+    }
+}
+
+#[test]
+fn renders_timestamp_in_microseconds() {
+    let kind = KindVarint::Timestamp(crate::timestamp_unit::TimestampUnit::Microseconds);
+
+    assert_eq!(
+        Some(crate::timestamp_unit::TimestampUnit::Microseconds),
+        KindVarint::suggest_timestamp_unit(1_600_000_000_000_000)
+    );
+    assert_eq!(
+        "2020-09-13T12:26:40Z",
+        kind.get_value_as_string(1_600_000_000_000_000)
+    );
+    assert_eq!(Some(kind), KindVarint::parse("timestamp µs"));
+}
+
+#[test]
+fn gives_twos_complement_bits_of_signed_values() {
+    let minus_one = u64::MAX as u128;
+
+    assert_eq!(
+        Some((0xffff_ffff, 32)),
+        KindVarint::Int32.get_integer_bits(minus_one)
+    );
+    assert_eq!(Some((u64::MAX, 64)), KindVarint::ZigZag.get_integer_bits(1));
+    assert_eq!(Some((1, 64)), KindVarint::ZigZag.get_integer_bits(2));
+    assert_eq!(None, KindVarint::Bool.get_integer_bits(1));
+}
@@ -0,0 +1,99 @@
+use crate::assert_variant;
+use crate::data::Data;
+use crate::map_view::*;
+use crate::model::{Decoding, DecodingState};
+use crate::proto_type::ProtoType;
+use protobuf::well_known_types::{Struct, Value};
+use protobuf::Message;
+
+fn decode_struct_with_two_fields() -> Decoding {
+    let mut number = Value::new();
+    number.set_number_value(1.0);
+    let mut text = Value::new();
+    text.set_string_value("a".into());
+    let mut object = Struct::new();
+    object.fields.insert("number".into(), number);
+    object.fields.insert("text".into(), text);
+    let buffer = object.write_to_bytes().unwrap();
+
+    Decoding::new(
+        Data::Message {
+            field_number: 0,
+            items: crate::decode::decode_message(&buffer).unwrap(),
+        }
+        .into(),
+    )
+}
+
+#[test]
+fn finds_repeated_key_value_messages() {
+    let decoding = decode_struct_with_two_fields();
+
+    if let DecodingState::Message { items, .. } = &decoding.root {
+        assert_eq!(vec![1], find_map_field_numbers(items));
+        assert!(find_map_field_numbers(&items[..1]).is_empty());
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn views_entries_as_map_with_guessed_types() {
+    let mut decoding = decode_struct_with_two_fields();
+
+    decoding.view_as_map(&[1]);
+
+    assert_eq!(
+        Some(&MapView {
+            key_type: ProtoType::String,
+            value_type: ProtoType::Message,
+        }),
+        decoding.map_views.get(&vec![1])
+    );
+
+    if let DecodingState::Message { items, .. } = &decoding.root {
+        if let DecodingState::Message { items, .. } = &items[0] {
+            assert_variant!(items[0], DecodingState::Utf8String { .. });
+            assert_variant!(items[1], DecodingState::Message { .. });
+        } else {
+            panic!()
+        }
+    } else {
+        panic!()
+    }
+
+    decoding.view_as_list(&[1]);
+    assert!(decoding.map_views.is_empty());
+}
+
+#[test]
+fn does_not_allow_floating_point_keys() {
+    assert!(is_valid_key_type(ProtoType::Int32));
+    assert!(is_valid_key_type(ProtoType::String));
+    assert!(!is_valid_key_type(ProtoType::Double));
+    assert!(!is_valid_key_type(ProtoType::Message));
+}
+
+#[test]
+fn guesses_bytes_for_random_values() {
+    let mut value = vec![0x0a, 0x80, 0x02];
+    value.extend(0..=255);
+    let mut entry = vec![0x0a, 0x01, b'a', 0x12, 0x83, 0x02];
+    entry.extend(value);
+    let mut buffer = vec![0x0a, 0x89, 0x02];
+    buffer.extend(entry);
+    let mut decoding = Decoding::new(
+        Data::Message {
+            field_number: 0,
+            items: crate::decode::decode_message(&buffer).unwrap(),
+        }
+        .into(),
+    );
+
+    decoding.view_as_map(&[1]);
+
+    assert_eq!(
+        Some(ProtoType::Bytes),
+        decoding.map_views.get(&vec![1]).map(|map| map.value_type)
+    );
+}
@@ -0,0 +1,32 @@
+use crate::message_pack;
+
+#[test]
+fn decodes_nested_document() {
+    // {"id": 300, "tags": ["x", nil], "ok": true, "t": -2, "f": 0.5}
+    let buffer = b"\x85\xa2id\xcd\x01\x2c\xa4tags\x92\xa1x\xc0\xa2ok\xc3\xa1t\xfe\xa1f\xcb\x3f\xe0\0\0\0\0\0\0";
+
+    assert_eq!(
+        r#"{"id": 300, "tags": ["x", null], "ok": true, "t": -2, "f": 0.5}"#,
+        message_pack::decode(buffer).unwrap().format()
+    );
+}
+
+#[test]
+fn decodes_signed_integers_and_extensions() {
+    assert_eq!(
+        "[-129, ext(-1, h'00000001')]",
+        message_pack::decode(b"\x92\xd1\xff\x7f\xd6\xff\x00\x00\x00\x01")
+            .unwrap()
+            .format()
+    );
+}
+
+#[test]
+fn reports_invalid_data() {
+    assert_eq!(
+        "Unused format 0xc1 at byte 1",
+        message_pack::decode(b"\xc1").unwrap_err().message
+    );
+    assert!(message_pack::decode(b"\xdd\xff\xff\xff\xff").is_err());
+    assert!(message_pack::decode(b"").is_err());
+}
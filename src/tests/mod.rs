@@ -1,16 +1,38 @@
+pub mod base64;
 pub mod binary_proto_object_loader;
+pub mod cbor;
 pub mod data;
 pub mod decode;
+pub mod dictionary;
+pub mod document;
+pub mod embedded_format;
+pub mod entropy;
 pub mod error;
+pub mod file_format;
+pub mod float_format;
 pub mod format;
+pub mod hex;
+pub mod integer_format;
+pub mod interpretation;
 pub mod key;
 pub mod kind32;
 pub mod kind64;
+pub mod kind_bytes;
+pub mod kind_message;
 pub mod kind_varint;
+pub mod map_view;
+pub mod message_pack;
 pub mod model;
+pub mod prost_export;
+pub mod schema;
+pub mod string_encoding;
+pub mod table_view;
+pub mod text_encoding;
+pub mod timestamp_unit;
 pub mod test_data;
 pub mod ui_message;
 pub mod update;
+pub mod validation;
 pub mod varint;
 pub mod view;
 pub mod wire_type;
@@ -76,7 +76,7 @@ fn formats_kind_for_unsigned_varint() {
         kind: KindVarint::Unsigned,
     };
 
-    assert_eq!("<varint unsign>", vm.get_formatted_kind())
+    assert_eq!("<varint unsign>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -88,7 +88,7 @@ fn formats_kind_for_zigzag_varint() {
         kind: KindVarint::ZigZag,
     };
 
-    assert_eq!("<varint zigzag>", vm.get_formatted_kind())
+    assert_eq!("<varint zigzag>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -100,7 +100,7 @@ fn formats_kind_for_fixed_32() {
         buffer: vec![],
     };
 
-    assert_eq!("<fix 32 unsign>", vm.get_formatted_kind())
+    assert_eq!("<fix 32 unsign>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -112,7 +112,7 @@ fn formats_kind_for_sfixed_32() {
         buffer: vec![],
     };
 
-    assert_eq!("<fix 32 sign>", vm.get_formatted_kind())
+    assert_eq!("<fix 32 sign>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -124,7 +124,7 @@ fn formats_kind_for_float() {
         buffer: vec![],
     };
 
-    assert_eq!("<fix 32 float>", vm.get_formatted_kind())
+    assert_eq!("<fix 32 float>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -136,7 +136,7 @@ fn formats_kind_for_buffer_32() {
         buffer: vec![],
     };
 
-    assert_eq!("<fix 32 raw>", vm.get_formatted_kind())
+    assert_eq!("<fix 32 raw>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -148,7 +148,7 @@ fn formats_kind_for_fixed_64() {
         buffer: vec![],
     };
 
-    assert_eq!("<fix 64 unsign>", vm.get_formatted_kind())
+    assert_eq!("<fix 64 unsign>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -160,7 +160,7 @@ fn formats_kind_for_sfixed_64() {
         buffer: vec![],
     };
 
-    assert_eq!("<fix 64 sign>", vm.get_formatted_kind())
+    assert_eq!("<fix 64 sign>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -172,7 +172,7 @@ fn formats_kind_for_double() {
         buffer: vec![],
     };
 
-    assert_eq!("<fix 64 double>", vm.get_formatted_kind())
+    assert_eq!("<fix 64 double>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -184,7 +184,7 @@ fn formats_kind_for_buffer_64() {
         buffer: vec![],
     };
 
-    assert_eq!("<fix 64 raw>", vm.get_formatted_kind())
+    assert_eq!("<fix 64 raw>", vm.get_formatted_kind(None))
 }
 
 #[test]
@@ -241,3 +241,401 @@ fn does_not_fail_for_empty_object() {
         vm.decode_as_message(uuid.clone());
     }
 }
+
+#[test]
+fn formats_field_number_with_name_from_dictionary() {
+    let vm = DecodingState::Varint {
+        field_number: 2,
+        value: 0,
+        uuid: Uuid::new_v4(),
+        kind: KindVarint::Unsigned,
+    };
+    let entry = crate::dictionary::FieldEntry {
+        name: Some("id".into()),
+        proto_type: Some(crate::proto_type::ProtoType::Int32),
+        ..Default::default()
+    };
+
+    assert_eq!("id (2)", vm.get_formatted_field_number(Some(&entry)));
+    assert_eq!("int32 <varint unsign>", vm.get_formatted_kind(Some(&entry)));
+}
+
+#[test]
+fn saves_annotation_for_every_node_with_the_same_path() {
+    let mut repeated_values = RepeatedValues::new();
+    repeated_values.param1.push("a".into());
+    repeated_values.param1.push("b".into());
+    let buffer = repeated_values.write_to_bytes().unwrap();
+
+    let mut decoding = Decoding::new(
+        data::Data::Message {
+            field_number: 0,
+            items: decode::decode_message(&buffer).unwrap(),
+        }
+        .into(),
+    );
+
+    let uuid = if let DecodingState::Message { items, .. } = &decoding.root {
+        items[0].get_uuid()
+    } else {
+        panic!()
+    };
+
+    decoding.start_annotation(uuid);
+    if let Some(editor) = &mut decoding.annotation_editor {
+        editor.name = "param1".into();
+        editor.proto_type = "string".into();
+        editor.note = "Free text".into();
+    }
+    decoding.save_annotation();
+
+    let entry = decoding.dictionary.get(&[1]).unwrap();
+    assert_eq!(Some("param1".to_string()), entry.name);
+    assert_eq!(Some("Free text".to_string()), entry.note);
+    assert!(decoding.annotation_editor.is_none());
+
+    if let DecodingState::Message { items, .. } = &decoding.root {
+        assert_variant!(items[0], DecodingState::Utf8String { .. });
+        assert_variant!(items[1], DecodingState::Utf8String { .. });
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn interprets_enum_fields_declared_in_schema() {
+    let mut phone_number = Person_PhoneNumber::new();
+    phone_number.set_field_type(Person_PhoneType::WORK);
+    let mut person = Person::new();
+    person.phones.push(phone_number);
+    let buffer = person.write_to_bytes().unwrap();
+
+    let mut decoding = Decoding::new(
+        data::Data::Message {
+            field_number: 0,
+            items: decode::decode_message(&buffer).unwrap(),
+        }
+        .into(),
+    );
+    decoding.schema_input = include_str!("test_data.proto").into();
+    decoding.load_schema();
+    decoding.change_root_message("protodec.Person".into());
+    decoding.dictionary.set(
+        vec![4, 2],
+        crate::dictionary::FieldEntry {
+            name: Some("kind".into()),
+            ..Default::default()
+        },
+    );
+
+    let entry = decoding.get_field_entry(&[4, 2]).unwrap();
+    assert_eq!(Some("kind".to_string()), entry.name);
+    assert_eq!(Some("WORK"), entry.get_enum_label(2));
+
+    if let DecodingState::Message { items, .. } = &decoding.root {
+        if let DecodingState::Message { items, .. } = &items[0] {
+            assert_variant!(
+                items[0],
+                DecodingState::Varint {
+                    kind: KindVarint::Enum,
+                    ..
+                }
+            );
+        } else {
+            panic!()
+        }
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn does_not_save_annotation_with_invalid_enum_labels() {
+    let mut decoding = Decoding::new(
+        data::Data::Message {
+            field_number: 0,
+            items: decode::decode_message(&[8, 1]).unwrap(),
+        }
+        .into(),
+    );
+
+    let uuid = if let DecodingState::Message { items, .. } = &decoding.root {
+        items[0].get_uuid()
+    } else {
+        panic!()
+    };
+
+    decoding.start_annotation(uuid);
+    if let Some(editor) = &mut decoding.annotation_editor {
+        editor.enum_labels = "WORK".into();
+    }
+    decoding.save_annotation();
+
+    assert!(decoding.annotation_editor.unwrap().error.is_some());
+    assert!(decoding.dictionary.get(&[1]).is_none());
+}
+
+#[test]
+fn unpacks_any_payload_with_schema_type() {
+    let mut person = Person::new();
+    person.set_name("John".into());
+    let mut any = protobuf::well_known_types::Any::new();
+    any.set_type_url("type.googleapis.com/protodec.Person".into());
+    any.set_value(person.write_to_bytes().unwrap());
+    let buffer = any.write_to_bytes().unwrap();
+
+    let root = DecodingState::from(data::Data::Chunk {
+        field_number: 0,
+        buffer,
+    });
+    let uuid = root.get_uuid();
+    let mut decoding = Decoding::new(root);
+    decoding.schema_input = include_str!("test_data.proto").into();
+    decoding.load_schema();
+    decoding.decode_as_message(uuid);
+
+    let scope = crate::model::SchemaScope {
+        message_name: "protodec.Person",
+        path_offset: 1,
+    };
+    let entry = decoding.get_field_entry_in(scope, &[2, 1]).unwrap();
+    assert_eq!(Some("name".to_string()), entry.name);
+
+    if let DecodingState::Message { items, kind, .. } = &decoding.root {
+        assert_eq!(crate::kind_message::KindMessage::Any, *kind);
+        if let DecodingState::Message { items, .. } = &items[1] {
+            assert_variant!(items[0], DecodingState::Utf8String { .. });
+        } else {
+            panic!()
+        }
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn applies_action_to_whole_repeated_group_when_asked() {
+    let mut repeated_values = RepeatedValues::new();
+    repeated_values.param1.push("a".into());
+    repeated_values.param2.push(1);
+    repeated_values.param1.push("b".into());
+    let buffer = repeated_values.write_to_bytes().unwrap();
+
+    let mut decoding = Decoding::new(
+        data::Data::Message {
+            field_number: 0,
+            items: decode::decode_message(&buffer).unwrap(),
+        }
+        .into(),
+    );
+
+    let (root_uuid, first_uuid) =
+        if let DecodingState::Message { items, uuid, .. } = &decoding.root {
+            (*uuid, items[0].get_uuid())
+        } else {
+            panic!()
+        };
+
+    assert_eq!(vec![first_uuid], decoding.get_action_targets(first_uuid));
+
+    decoding.grouped_actions.insert((root_uuid, 1));
+    assert_eq!(2, decoding.get_action_targets(first_uuid).len());
+
+    decoding.decode_as_utf8_string(first_uuid);
+
+    if let DecodingState::Message { items, .. } = &decoding.root {
+        assert_variant!(items[0], DecodingState::Utf8String { .. });
+        assert_variant!(items[1], DecodingState::Utf8String { .. });
+        assert_variant!(items[2], DecodingState::Varint { .. });
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn applies_interpretation_to_all_similar_nodes_including_later_ones() {
+    let mut person = Person::new();
+    for number in &["123", "456"] {
+        let mut phone = Person_PhoneNumber::new();
+        phone.set_number((*number).into());
+        person.phones.push(phone);
+    }
+    let buffer = person.write_to_bytes().unwrap();
+
+    let mut decoding = Decoding::new(
+        data::Data::Message {
+            field_number: 0,
+            items: decode::decode_message(&buffer).unwrap(),
+        }
+        .into(),
+    );
+
+    let phone_uuids: Vec<Uuid> = decoding
+        .root
+        .get_message_items()
+        .unwrap()
+        .iter()
+        .map(|item| item.get_uuid())
+        .collect();
+
+    decoding.decode_as_message(phone_uuids[0]);
+
+    let number_uuid = decoding
+        .root
+        .find_mut(phone_uuids[0])
+        .and_then(|phone| phone.get_message_items())
+        .map(|items| items[0].get_uuid())
+        .unwrap();
+
+    decoding.apply_to_similar = true;
+    decoding.decode_as_utf8_string(number_uuid);
+    decoding.apply_to_similar = false;
+
+    assert_eq!(
+        Some(&interpretation::Interpretation::Utf8String),
+        decoding.remembered_interpretations.get(&vec![4, 1])
+    );
+
+    decoding.decode_as_message(phone_uuids[1]);
+
+    let second_phone_items = decoding
+        .root
+        .find_mut(phone_uuids[1])
+        .and_then(|phone| phone.get_message_items())
+        .unwrap();
+    assert_variant!(second_phone_items[0], DecodingState::Utf8String { .. });
+
+    decoding.forget_interpretation(&[4, 1]);
+    assert!(decoding.remembered_interpretations.is_empty());
+}
+
+#[test]
+fn interprets_varints_with_declared_types() {
+    let mut different_numbers = DifferentNumbersWrapper::new();
+    different_numbers.set_value_int32(-5);
+    different_numbers.set_value_int64(-6);
+    different_numbers.set_value_uint32(7);
+    let buffer = different_numbers.write_to_bytes().unwrap();
+
+    let mut state: DecodingState = data::Data::Message {
+        field_number: 0,
+        items: decode::decode_message(&buffer).unwrap(),
+    }
+    .into();
+
+    let dictionary = dictionary::FieldDictionary::parse(
+        r#"{ "2": "a: int32", "3": "b: int64", "4": "c: uint32" }"#,
+    )
+    .unwrap();
+    state.apply_dictionary(&dictionary, &[]);
+
+    let values: Vec<String> = state
+        .get_message_items()
+        .unwrap()
+        .iter()
+        .map(|item| item.get_scalar_value_as_string().unwrap())
+        .collect();
+
+    assert_eq!(vec!["-5", "-6", "7"], values);
+}
+
+#[test]
+fn decodes_chunk_as_uuid_only_when_it_fits() {
+    let uuid = Uuid::new_v4();
+    let mut state = DecodingState::Chunk {
+        buffer: vec![1, 2, 3, 4],
+        uuid,
+        field_number: 1,
+    };
+
+    state.decode_as_bytes(uuid, kind_bytes::KindBytes::Uuid);
+    assert_variant!(state, DecodingState::Chunk { .. });
+
+    state.decode_as_bytes(uuid, kind_bytes::KindBytes::IpAddress);
+    assert_eq!(Some("1.2.3.4".to_string()), state.get_scalar_value_as_string());
+}
+
+#[test]
+fn decodes_invalid_utf8_as_lossy_text() {
+    let uuid = Uuid::new_v4();
+    let mut state = DecodingState::Chunk {
+        buffer: b"ab\xff".to_vec(),
+        uuid,
+        field_number: 1,
+    };
+
+    state.decode_as_utf8_string(uuid);
+
+    assert_variant!(
+        state,
+        DecodingState::Bytes {
+            kind: kind_bytes::KindBytes::Utf8Lossy,
+            ..
+        }
+    );
+}
+
+#[test]
+fn decodes_lossy_text_as_message() {
+    let uuid = Uuid::new_v4();
+    let mut state = DecodingState::Chunk {
+        buffer: vec![0x08, 0x96, 0x01],
+        uuid,
+        field_number: 1,
+    };
+
+    state.decode_as_utf8_string(uuid);
+    state.decode_as_message(uuid);
+
+    match state {
+        DecodingState::Message { items, .. } => assert_variant!(
+            items[0],
+            DecodingState::Varint {
+                value: 150,
+                field_number: 1,
+                ..
+            }
+        ),
+        _ => panic!("the lossy text is not decoded as a message"),
+    }
+}
+
+#[test]
+fn turns_bytes_back_into_chunk() {
+    let uuid = Uuid::new_v4();
+    let mut decoding = Decoding::new(DecodingState::Chunk {
+        buffer: vec![0x08, 0x96, 0x01],
+        uuid,
+        field_number: 1,
+    });
+
+    decoding.decode_as_bytes(uuid, kind_bytes::KindBytes::Hex);
+    assert_variant!(decoding.root, DecodingState::Bytes { .. });
+
+    decoding.decode_as_chunk(uuid);
+    assert_variant!(decoding.root, DecodingState::Chunk { .. });
+}
+
+#[test]
+fn decodes_base64_string_as_nested_message() {
+    let uuid = Uuid::new_v4();
+    let mut decoding = Decoding::new(DecodingState::Utf8String {
+        value: "CJYB".to_string(),
+        uuid,
+        field_number: 1,
+    });
+
+    decoding.decode_string_as_message(uuid, string_encoding::StringEncoding::Base64);
+
+    match &decoding.root {
+        DecodingState::Message { items, .. } => assert_variant!(
+            items[0],
+            DecodingState::Varint {
+                value: 150,
+                field_number: 1,
+                ..
+            }
+        ),
+        _ => panic!("the string is not decoded as a message"),
+    }
+}
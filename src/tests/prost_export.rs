@@ -0,0 +1,279 @@
+use crate::data::Data;
+use crate::model::DecodingState;
+use crate::prost_export::FieldInfo;
+use crate::tests::test_data::*;
+use protobuf::Message;
+
+fn decode_root(buffer: &[u8]) -> DecodingState {
+    Data::Message {
+        field_number: 0,
+        items: crate::decode::decode_message(buffer).unwrap(),
+    }
+    .into()
+}
+
+#[test]
+fn exports_scalar_fields_with_tags() {
+    let mut object = ShortMessageForExample::new();
+    object.set_name("John Doe".into());
+    object.set_age(30);
+    let buffer = object.write_to_bytes().unwrap();
+
+    let actual = crate::prost_export::export_as_prost_structs(&decode_root(&buffer), &|_| None);
+
+    assert_eq!(
+        "#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Root {
+    #[prost(bytes = \"vec\", tag = \"1\")]
+    pub field_1: Vec<u8>,
+    #[prost(uint64, tag = \"2\")]
+    pub field_2: u64,
+}
+",
+        actual
+    );
+}
+
+#[test]
+fn exports_nested_messages_into_module() {
+    let mut string_wrapper = StringWrapper::new();
+    string_wrapper.set_value("117".into());
+    let mut string_wrapper_wrapper = StringWrapperWrapper::new();
+    string_wrapper_wrapper.set_value(string_wrapper);
+    let buffer = string_wrapper_wrapper.write_to_bytes().unwrap();
+
+    let mut root = decode_root(&buffer);
+    let uuid = if let DecodingState::Message { items, .. } = &root {
+        if let DecodingState::Chunk { uuid, .. } = items[0] {
+            uuid
+        } else {
+            panic!()
+        }
+    } else {
+        panic!()
+    };
+    root.decode_as_message(uuid);
+
+    let actual = crate::prost_export::export_as_prost_structs(&root, &|_| None);
+
+    assert!(actual.contains("    #[prost(message, optional, tag = \"1\")]\n"));
+    assert!(actual.contains("    pub field_1: Option<root::Field1>,\n"));
+    assert!(actual.contains("/// Nested message and enum types in `Root`.\npub mod root {\n"));
+    assert!(actual.contains("    pub struct Field1 {\n"));
+}
+
+#[test]
+fn marks_fields_occurring_several_times_as_repeated() {
+    let mut repeated_values = RepeatedValues::new();
+    repeated_values.param1.push("a".into());
+    repeated_values.param1.push("b".into());
+    let buffer = repeated_values.write_to_bytes().unwrap();
+
+    let actual = crate::prost_export::export_as_prost_structs(&decode_root(&buffer), &|_| None);
+
+    assert!(actual.contains("    #[prost(bytes = \"vec\", repeated, tag = \"1\")]\n"));
+    assert!(actual.contains("    pub field_1: Vec<Vec<u8>>,\n"));
+}
+
+#[test]
+fn does_not_export_undecoded_root() {
+    let state: DecodingState = Data::Chunk {
+        field_number: 0,
+        buffer: vec![],
+    }
+    .into();
+
+    let actual = crate::prost_export::export_as_prost_structs(&state, &|_| None);
+
+    assert!(actual.starts_with("//"));
+}
+
+#[test]
+fn exports_chunks_shown_as_bytes_as_bytes() {
+    let mut object = ShortMessageForExample::new();
+    object.set_name("John Doe".into());
+    let buffer = object.write_to_bytes().unwrap();
+
+    let mut root = decode_root(&buffer);
+    let uuid = if let DecodingState::Message { items, .. } = &root {
+        items[0].get_uuid()
+    } else {
+        panic!()
+    };
+    root.decode_as_bytes(uuid, crate::kind_bytes::KindBytes::Hex);
+
+    let actual = crate::prost_export::export_as_prost_structs(&root, &|_| None);
+
+    assert!(actual.contains("    #[prost(bytes = \"vec\", tag = \"1\")]\n"));
+    assert!(actual.contains("    pub field_1: Vec<u8>,\n"));
+}
+
+#[test]
+fn exports_well_known_types_as_prost_types() {
+    let mut timestamp = protobuf::well_known_types::Timestamp::new();
+    timestamp.set_seconds(1_600_000_000);
+    let mut person = Person::new();
+    person.set_last_updated(timestamp);
+    let buffer = person.write_to_bytes().unwrap();
+
+    let mut root = decode_root(&buffer);
+    let uuid = if let DecodingState::Message { items, .. } = &root {
+        items[0].get_uuid()
+    } else {
+        panic!()
+    };
+    root.decode_as_message(uuid);
+    root.select_message_kind(uuid, crate::kind_message::KindMessage::Timestamp);
+
+    let actual = crate::prost_export::export_as_prost_structs(&root, &|_| None);
+
+    assert_eq!(
+        "#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Root {
+    #[prost(message, optional, tag = \"5\")]
+    pub field_5: Option<::prost_types::Timestamp>,
+}
+",
+        actual
+    );
+}
+
+#[test]
+fn exports_known_names_and_enums() {
+    let mut object = ShortMessageForExample::new();
+    object.set_name("John Doe".into());
+    object.set_age(30);
+    let buffer = object.write_to_bytes().unwrap();
+
+    let mut root = decode_root(&buffer);
+    let uuid = if let DecodingState::Message { items, .. } = &root {
+        items[1].get_uuid()
+    } else {
+        panic!()
+    };
+    root.select_varint_kind(uuid, crate::kind_varint::KindVarint::Enum);
+
+    let actual = crate::prost_export::export_as_prost_structs(&root, &|path| match path {
+        [1] => Some(FieldInfo {
+            name: Some("fullName".into()),
+            ..Default::default()
+        }),
+        [2] => Some(FieldInfo {
+            name: Some("age group".into()),
+            enum_labels: vec![(20, "TWENTIES".to_string())].into_iter().collect(),
+        }),
+        _ => None,
+    });
+
+    assert_eq!(
+        "#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Root {
+    #[prost(bytes = \"vec\", tag = \"1\")]
+    pub full_name: Vec<u8>,
+    #[prost(enumeration = \"root::AgeGroup\", tag = \"2\")]
+    pub age_group: i32,
+}
+/// Nested message and enum types in `Root`.
+pub mod root {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum AgeGroup {
+        Twenties = 20,
+        Value30 = 30,
+    }
+}
+",
+        actual
+    );
+}
+#[test]
+fn exports_unique_enum_variants_within_i32_range() {
+    let mut root = decode_root(&[0x10, 30]);
+    let uuid = if let DecodingState::Message { items, .. } = &root {
+        items[0].get_uuid()
+    } else {
+        panic!()
+    };
+    root.select_varint_kind(uuid, crate::kind_varint::KindVarint::Enum);
+
+    let actual = crate::prost_export::export_as_prost_structs(&root, &|path| match path {
+        [2] => Some(FieldInfo {
+            name: Some("age group".into()),
+            enum_labels: vec![
+                (-3_000_000_000, "TOO_SMALL".to_string()),
+                (20, "VALUE_30".to_string()),
+                (3_000_000_000, "TOO_BIG".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        }),
+        _ => None,
+    });
+
+    assert_eq!(
+        "#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Root {
+    #[prost(enumeration = \"root::AgeGroup\", tag = \"2\")]
+    pub age_group: i32,
+}
+/// Nested message and enum types in `Root`.
+pub mod root {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum AgeGroup {
+        Value30 = 20,
+        Value302 = 30,
+    }
+}
+",
+        actual
+    );
+}
+
+#[test]
+fn exports_enums_outside_i32_range_as_int64() {
+    let mut root = decode_root(&[0x10, 0x80, 0x80, 0x80, 0x80, 0x08]);
+    let uuid = if let DecodingState::Message { items, .. } = &root {
+        items[0].get_uuid()
+    } else {
+        panic!()
+    };
+    root.select_varint_kind(uuid, crate::kind_varint::KindVarint::Enum);
+
+    let actual = crate::prost_export::export_as_prost_structs(&root, &|_| None);
+
+    assert_eq!(
+        "#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Root {
+    #[prost(int64, tag = \"2\")]
+    pub field_2: i64,
+}
+",
+        actual
+    );
+}
+
+#[test]
+fn exports_unique_field_names() {
+    let root = decode_root(&[0x08, 1, 0x10, 2]);
+
+    let actual = crate::prost_export::export_as_prost_structs(&root, &|path| match path {
+        [1] => Some(FieldInfo {
+            name: Some("field 2".into()),
+            ..Default::default()
+        }),
+        _ => None,
+    });
+
+    assert_eq!(
+        "#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Root {
+    #[prost(uint64, tag = \"1\")]
+    pub field_2: u64,
+    #[prost(uint64, tag = \"2\")]
+    pub field_2_2: u64,
+}
+",
+        actual
+    );
+}
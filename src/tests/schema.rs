@@ -0,0 +1,93 @@
+use crate::proto_type::ProtoType;
+use crate::schema::Schema;
+
+const TEST_DATA_PROTO: &str = include_str!("test_data.proto");
+
+#[test]
+fn parses_messages_and_enums_of_test_data() {
+    let schema = Schema::parse(TEST_DATA_PROTO).unwrap();
+
+    let person = schema.find_message("protodec.Person").unwrap();
+    assert_eq!(5, person.fields.len());
+
+    let phones = person.find_field(4).unwrap();
+    assert_eq!("phones", phones.name);
+    assert!(phones.repeated);
+    assert_eq!(ProtoType::Message, phones.proto_type);
+    assert_eq!(
+        Some("protodec.Person.PhoneNumber".to_string()),
+        phones.type_name
+    );
+
+    let phone_type = schema
+        .find_message("protodec.Person.PhoneNumber")
+        .unwrap()
+        .find_field(2)
+        .unwrap();
+    assert_eq!(ProtoType::Enum, phone_type.proto_type);
+
+    let enumeration = schema.find_enum("protodec.Person.PhoneType").unwrap();
+    assert_eq!(Some(&"WORK".to_string()), enumeration.values.get(&2));
+}
+
+#[test]
+fn parses_map_fields_oneofs_and_options() {
+    let schema = Schema::parse(
+        r#"
+        syntax = "proto3";
+        /* Block comment */
+        message Sample {
+            map<string, int32> counters = 1;
+            oneof payload {
+                string text = 2;
+                bytes data = 3 [deprecated = true];
+            }
+            reserved 4, 5;
+            enum Kind { option allow_alias = true; NONE = 0; NEGATIVE = -1; }
+            Kind kind = 6;
+        }
+        "#,
+    )
+    .unwrap();
+
+    let sample = schema.find_message("Sample").unwrap();
+    assert_eq!(4, sample.fields.len());
+    assert_eq!(
+        Some("Sample.CountersEntry".to_string()),
+        sample.find_field(1).unwrap().type_name
+    );
+    assert!(sample.find_field(1).unwrap().repeated);
+    assert_eq!(ProtoType::Bytes, sample.find_field(3).unwrap().proto_type);
+    assert_eq!(ProtoType::Enum, sample.find_field(6).unwrap().proto_type);
+
+    let entry = schema.find_message("Sample.CountersEntry").unwrap();
+    assert_eq!(ProtoType::String, entry.find_field(1).unwrap().proto_type);
+    assert_eq!(ProtoType::Int32, entry.find_field(2).unwrap().proto_type);
+
+    let kind = schema.find_enum("Sample.Kind").unwrap();
+    assert_eq!(Some(&"NEGATIVE".to_string()), kind.values.get(&-1));
+}
+
+#[test]
+fn does_not_parse_invalid_schema() {
+    assert!(Schema::parse("message {").is_err());
+    assert!(Schema::parse("message A { string name = ; }").is_err());
+    assert!(Schema::parse("message A { string name = 1; ").is_err());
+}
+
+#[test]
+fn describes_field_by_path_with_enum_labels() {
+    let schema = Schema::parse(TEST_DATA_PROTO).unwrap();
+
+    let entry = schema
+        .get_field_entry("protodec.AddressBook", &[1, 4, 2])
+        .unwrap();
+
+    assert_eq!(Some("type".to_string()), entry.name);
+    assert_eq!(Some(ProtoType::Enum), entry.proto_type);
+    assert_eq!(Some("HOME"), entry.get_enum_label(1));
+    assert!(schema
+        .get_field_entry("protodec.AddressBook", &[1, 9])
+        .is_none());
+    assert!(schema.get_field_entry("protodec.AddressBook", &[]).is_none());
+}
@@ -0,0 +1,23 @@
+use crate::string_encoding::StringEncoding;
+
+#[test]
+fn decodes_hex_with_whitespace() {
+    assert_eq!(
+        Some(vec![0x08, 0x96, 0x01]),
+        StringEncoding::Hex.decode("08 96 01")
+    );
+    assert_eq!(None, StringEncoding::Hex.decode("089"));
+    assert_eq!(None, StringEncoding::Hex.decode("0g"));
+    assert_eq!(None, StringEncoding::Hex.decode(""));
+}
+
+#[test]
+fn suggests_encodings_of_plausible_messages() {
+    assert_eq!(Some(StringEncoding::Hex), StringEncoding::suggest("089601"));
+    assert_eq!(
+        Some(StringEncoding::Base64),
+        StringEncoding::suggest("CJYB")
+    );
+    assert_eq!(None, StringEncoding::suggest("hello world"));
+    assert_eq!(None, StringEncoding::suggest("AA"));
+}
@@ -0,0 +1,129 @@
+use crate::data::Data;
+use crate::model::DecodingState;
+use crate::table_view::*;
+
+fn element(items: Vec<Data>) -> DecodingState {
+    Data::Message {
+        field_number: 1,
+        items,
+    }
+    .into()
+}
+
+fn varint(field_number: u128, value: u128) -> Data {
+    Data::Varint {
+        field_number,
+        value,
+    }
+}
+
+fn format_value(state: &DecodingState, _: &[u128]) -> String {
+    state.get_scalar_value_as_string().unwrap_or_default()
+}
+
+#[test]
+fn builds_columns_from_field_paths() {
+    let first = element(vec![
+        varint(2, 10),
+        Data::Message {
+            field_number: 4,
+            items: vec![varint(1, 7)],
+        },
+    ]);
+    let second = element(vec![varint(2, 9), varint(3, 1), varint(3, 2)]);
+
+    let table = Table::from_elements(&[&first, &second], format_value);
+
+    assert_eq!(vec![vec![2], vec![3], vec![4, 1]], table.columns);
+    assert_eq!(
+        vec![
+            vec!["10".to_string(), "".to_string(), "7".to_string()],
+            vec!["9".to_string(), "1, 2".to_string(), "".to_string()],
+        ],
+        table.rows
+    );
+}
+
+#[test]
+fn sorts_numbers_by_value() {
+    let first = element(vec![varint(1, 10)]);
+    let second = element(vec![varint(1, 9)]);
+    let mut table = Table::from_elements(&[&first, &second], format_value);
+
+    let mut table_view = TableView::default();
+    table_view.sort_by(vec![1]);
+    table.sort(&table_view);
+    assert_eq!("9", table.rows[0][0]);
+
+    table_view.sort_by(vec![1]);
+    table.sort(&table_view);
+    assert_eq!("10", table.rows[0][0]);
+}
+
+#[test]
+fn sorts_numbers_before_text_in_mixed_columns() {
+    let mut table = Table {
+        columns: vec![vec![1]],
+        rows: ["1a", "10", "NaN", "9", "", "b", "-1"]
+            .iter()
+            .map(|cell| vec![cell.to_string()])
+            .collect(),
+    };
+
+    let mut table_view = TableView::default();
+    table_view.sort_by(vec![1]);
+    table.sort(&table_view);
+
+    assert_eq!(
+        vec!["-1", "9", "10", "NaN", "", "1a", "b"],
+        table
+            .rows
+            .iter()
+            .map(|row| row[0].as_str())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn exports_csv_with_escaped_cells() {
+    let table = Table {
+        columns: vec![vec![1], vec![2]],
+        rows: vec![vec!["a,b".to_string(), "say \"hi\"".to_string()]],
+    };
+
+    assert_eq!(
+        "name (1),2\r\n\"a,b\",\"say \"\"hi\"\"\"\r\n",
+        table.to_csv(&["name (1)".to_string(), "2".to_string()])
+    );
+}
+
+#[test]
+fn fits_only_elements_holding_messages() {
+    let message = element(vec![varint(1, 150)]);
+    let chunk: DecodingState = Data::Chunk {
+        field_number: 1,
+        buffer: vec![0x08, 0x96, 0x01],
+    }
+    .into();
+    let mut string = chunk.clone();
+    let uuid = string.get_uuid();
+    string.decode_as_utf8_string(uuid);
+
+    assert!(Table::fits(&[&message, &chunk]));
+    assert!(!Table::fits(&[&message, &string]));
+    assert!(!Table::fits(&[&string, &string]));
+}
+
+#[test]
+fn does_not_fit_random_bytes_that_parse_as_a_message() {
+    // A single length-delimited field wrapping every byte value once.
+    let mut buffer = vec![0x0a, 0x80, 0x02];
+    buffer.extend(0..=255);
+    let chunk: DecodingState = Data::Chunk {
+        field_number: 1,
+        buffer,
+    }
+    .into();
+
+    assert!(!Table::fits(&[&chunk]));
+}
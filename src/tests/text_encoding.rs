@@ -0,0 +1,35 @@
+use crate::text_encoding::*;
+
+#[test]
+fn reports_invalid_utf8_at_byte_offsets() {
+    let segments = decode_utf8_lossy(b"ab\xffcd\xe2\x82");
+
+    assert_eq!(
+        vec![
+            TextSegment::Text("ab".into()),
+            TextSegment::Invalid {
+                offset: 2,
+                bytes: vec![0xff]
+            },
+            TextSegment::Text("cd".into()),
+            TextSegment::Invalid {
+                offset: 5,
+                bytes: vec![0xe2, 0x82]
+            },
+        ],
+        segments
+    );
+    assert_eq!("ab\\xffcd\\xe2\\x82", format_segments(&segments));
+    assert_eq!(
+        vec![TextSegment::Text("ok".into())],
+        decode_utf8_lossy(b"ok")
+    );
+}
+
+#[test]
+fn decodes_legacy_encodings() {
+    assert_eq!("café", decode_latin1(b"caf\xe9"));
+    assert_eq!("hé", decode_utf16(&[0x68, 0x00, 0xe9, 0x00], false));
+    assert_eq!("hé", decode_utf16(&[0x00, 0x68, 0x00, 0xe9], true));
+    assert_eq!("a\\tb\\x00\\\"", escape_ascii(b"a\tb\x00\""));
+}
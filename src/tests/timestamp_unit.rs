@@ -0,0 +1,44 @@
+use crate::timestamp_unit::TimestampUnit;
+
+#[test]
+fn formats_value_in_every_unit() {
+    assert_eq!(
+        Some("2020-09-13T12:26:40Z".to_string()),
+        TimestampUnit::Seconds.format(1_600_000_000)
+    );
+    assert_eq!(
+        Some("2020-09-13T12:26:40.123Z".to_string()),
+        TimestampUnit::Milliseconds.format(1_600_000_000_123)
+    );
+    assert_eq!(
+        Some("1969-12-31T23:59:59.999999Z".to_string()),
+        TimestampUnit::Microseconds.format(-1)
+    );
+    assert_eq!(
+        Some("2020-09-13T12:26:40.000000001Z".to_string()),
+        TimestampUnit::Nanoseconds.format(1_600_000_000_000_000_001)
+    );
+    assert_eq!(None, TimestampUnit::Seconds.format(i64::MAX as i128));
+}
+
+#[test]
+fn suggests_unit_with_plausible_date() {
+    assert_eq!(
+        Some(TimestampUnit::Seconds),
+        TimestampUnit::suggest(1_600_000_000)
+    );
+    assert_eq!(
+        Some(TimestampUnit::Milliseconds),
+        TimestampUnit::suggest(1_600_000_000_123)
+    );
+    assert_eq!(
+        Some(TimestampUnit::Microseconds),
+        TimestampUnit::suggest(1_600_000_000_123_456)
+    );
+    assert_eq!(
+        Some(TimestampUnit::Nanoseconds),
+        TimestampUnit::suggest(1_600_000_000_123_456_789)
+    );
+    assert_eq!(None, TimestampUnit::suggest(150));
+    assert_eq!(None, TimestampUnit::suggest(-1_600_000_000));
+}
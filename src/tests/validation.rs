@@ -0,0 +1,88 @@
+use crate::data::Data;
+use crate::model::DecodingState;
+use crate::schema::Schema;
+use crate::tests::test_data::*;
+use crate::validation::*;
+use crate::wire_type::WireType;
+use protobuf::Message;
+
+const TEST_DATA_PROTO: &str = include_str!("test_data.proto");
+
+fn decode_root(buffer: &[u8]) -> DecodingState {
+    Data::Chunk {
+        field_number: 0,
+        buffer: buffer.to_vec(),
+    }
+    .into()
+}
+
+fn person_buffer() -> Vec<u8> {
+    let mut person = Person::new();
+    person.set_name("Ivan".into());
+    person.set_id(300);
+    let mut phone = Person_PhoneNumber::new();
+    phone.set_number("89069062666".into());
+    phone.set_field_type(Person_PhoneType::WORK);
+    person.phones.push(phone.clone());
+    person.phones.push(phone);
+    person.write_to_bytes().unwrap()
+}
+
+#[test]
+fn reports_nothing_for_matching_message() {
+    let schema = Schema::parse(TEST_DATA_PROTO).unwrap();
+
+    let actual = validate(&schema, "protodec.Person", &decode_root(&person_buffer()));
+
+    assert_eq!(Vec::<ValidationIssue>::new(), actual);
+}
+
+#[test]
+fn reports_wire_type_mismatches_and_duplicates() {
+    let schema = Schema::parse(TEST_DATA_PROTO).unwrap();
+
+    let actual = validate(
+        &schema,
+        "protodec.DifferentNumbersWrapper",
+        &decode_root(&person_buffer()),
+    );
+
+    assert!(actual.contains(&ValidationIssue::WireTypeMismatch {
+        path: vec![1],
+        field_name: "value_double".into(),
+        declared: "double".into(),
+        received: WireType::LengthDelimited,
+    }));
+    assert!(actual.contains(&ValidationIssue::DuplicatedField {
+        path: vec![4],
+        field_name: "value_uint32".into(),
+        count: 2,
+    }));
+}
+
+#[test]
+fn reports_unknown_fields_in_nested_messages() {
+    let schema =
+        Schema::parse("message Outer { Inner inner = 1; } message Inner { string name = 1; }")
+            .unwrap();
+
+    let mut ta = Ta::new();
+    let mut tb = Ta_Tb::new();
+    tb.set_name("Ivan".into());
+    tb.set_email("ivan@smirnov.com".into());
+    ta.set_tb(tb);
+
+    let actual = validate(
+        &schema,
+        "Outer",
+        &decode_root(&ta.write_to_bytes().unwrap()),
+    );
+
+    assert_eq!(
+        vec![ValidationIssue::UnknownField {
+            path: vec![1, 3],
+            message_name: "Inner".into(),
+        }],
+        actual
+    );
+}
@@ -2,7 +2,7 @@
 fn correctly_takes_varint_300() {
     let buffer = [0b_1010_1100, 0b_0000_0010];
 
-    let actual = crate::varint::take_varint(&buffer);
+    let actual = crate::varint::take_varint(&buffer).unwrap();
 
     assert_eq!(300, actual.0);
     assert_eq!(0, actual.1.len());
@@ -12,7 +12,7 @@ fn correctly_takes_varint_300() {
 fn correctly_takes_varint_1() {
     let buffer = [0b_0001];
 
-    let actual = crate::varint::take_varint(&buffer);
+    let actual = crate::varint::take_varint(&buffer).unwrap();
 
     assert_eq!(1, actual.0);
     assert_eq!(0, actual.1.len());
@@ -22,7 +22,7 @@ fn correctly_takes_varint_1() {
 fn returns_correct_remaining_tail() {
     let buffer = [0b_0001, 0, 0, 0, 0];
 
-    let actual = crate::varint::take_varint(&buffer);
+    let actual = crate::varint::take_varint(&buffer).unwrap();
 
     assert_eq!(4, actual.1.len());
 }
@@ -31,7 +31,51 @@ fn returns_correct_remaining_tail() {
 fn returns_correct_remaining_tail_2() {
     let buffer = [0b_0001, 255, 128, 64, 32];
 
-    let actual = crate::varint::take_varint(&buffer);
+    let actual = crate::varint::take_varint(&buffer).unwrap();
 
     assert_eq!(4, actual.1.len());
 }
+
+#[test]
+fn fails_on_empty_buffer() {
+    let actual = crate::varint::take_varint(&[]);
+
+    assert_eq!(
+        "Error. Buffer can't be empty here.",
+        actual.unwrap_err().message
+    );
+}
+
+#[test]
+fn fails_on_unterminated_varint() {
+    let actual = crate::varint::take_varint(&[0xff, 0x80]);
+
+    assert_eq!(
+        "Error. Varint is not terminated.",
+        actual.unwrap_err().message
+    );
+}
+
+#[test]
+fn fails_on_varint_longer_than_ten_bytes() {
+    let buffer = [0xff; 20];
+
+    let actual = crate::varint::take_varint(&buffer);
+
+    assert_eq!(
+        "Error. Varint can't be longer than 10 bytes.",
+        actual.unwrap_err().message
+    );
+}
+
+#[test]
+fn takes_ten_byte_varint() {
+    let buffer = [
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x08,
+    ];
+
+    let (value, rest) = crate::varint::take_varint(&buffer).unwrap();
+
+    assert_eq!(u64::MAX as u128, value);
+    assert_eq!(1, rest.len());
+}
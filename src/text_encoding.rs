@@ -0,0 +1,92 @@
+#[derive(Debug, PartialEq)]
+pub enum TextSegment {
+    Text(String),
+    Invalid { offset: usize, bytes: Vec<u8> },
+}
+
+// Valid runs are kept as text and every invalid sequence is reported with
+// its byte offset, so the broken bytes can be found in the raw chunk.
+pub fn decode_utf8_lossy(buffer: &[u8]) -> Vec<TextSegment> {
+    let mut segments = vec![];
+    let mut offset = 0;
+
+    while offset < buffer.len() {
+        match std::str::from_utf8(&buffer[offset..]) {
+            Ok(text) => {
+                segments.push(TextSegment::Text(text.to_string()));
+                break;
+            }
+            Err(err) => {
+                let valid_up_to = offset + err.valid_up_to();
+                if valid_up_to > offset {
+                    let text = std::str::from_utf8(&buffer[offset..valid_up_to]).unwrap();
+                    segments.push(TextSegment::Text(text.to_string()));
+                }
+
+                let invalid_end = match err.error_len() {
+                    Some(error_len) => valid_up_to + error_len,
+                    None => buffer.len(),
+                };
+                segments.push(TextSegment::Invalid {
+                    offset: valid_up_to,
+                    bytes: buffer[valid_up_to..invalid_end].to_vec(),
+                });
+                offset = invalid_end;
+            }
+        }
+    }
+
+    segments
+}
+
+pub fn format_segments(segments: &[TextSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            TextSegment::Text(text) => text.clone(),
+            TextSegment::Invalid { bytes, .. } => escape_bytes(bytes),
+        })
+        .collect()
+}
+
+pub fn decode_latin1(buffer: &[u8]) -> String {
+    buffer.iter().map(|byte| *byte as char).collect()
+}
+
+// Unpaired surrogates are replaced, like in `String::from_utf16_lossy`.
+pub fn decode_utf16(buffer: &[u8], big_endian: bool) -> String {
+    let units: Vec<u16> = buffer
+        .chunks(2)
+        .map(|pair| {
+            if big_endian {
+                u16::from_be_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_le_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+
+    String::from_utf16_lossy(&units)
+}
+
+pub fn escape_ascii(buffer: &[u8]) -> String {
+    buffer
+        .iter()
+        .map(|byte| match byte {
+            b'\n' => "\\n".to_string(),
+            b'\r' => "\\r".to_string(),
+            b'\t' => "\\t".to_string(),
+            b'\\' => "\\\\".to_string(),
+            b'"' => "\\\"".to_string(),
+            0x20..=0x7e => (*byte as char).to_string(),
+            _ => escape_bytes(&[*byte]),
+        })
+        .collect()
+}
+
+fn escape_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("\\x{:02x}", byte))
+        .collect()
+}
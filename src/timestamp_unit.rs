@@ -0,0 +1,74 @@
+use crate::kind_message::{
+    self, MAX_TIMESTAMP_SECONDS, MIN_TIMESTAMP_SECONDS, SUGGESTED_TIMESTAMP_SECONDS,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TimestampUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl TimestampUnit {
+    pub fn all() -> &'static [TimestampUnit] {
+        &[
+            TimestampUnit::Seconds,
+            TimestampUnit::Milliseconds,
+            TimestampUnit::Microseconds,
+            TimestampUnit::Nanoseconds,
+        ]
+    }
+
+    pub fn to_string(self) -> &'static str {
+        match self {
+            TimestampUnit::Seconds => "s",
+            TimestampUnit::Milliseconds => "ms",
+            TimestampUnit::Microseconds => "µs",
+            TimestampUnit::Nanoseconds => "ns",
+        }
+    }
+
+    fn per_second(self) -> i128 {
+        match self {
+            TimestampUnit::Seconds => 1,
+            TimestampUnit::Milliseconds => 1_000,
+            TimestampUnit::Microseconds => 1_000_000,
+            TimestampUnit::Nanoseconds => 1_000_000_000,
+        }
+    }
+
+    fn split(self, value: i128) -> (i128, i128) {
+        let per_second = self.per_second();
+        let nanos = value.rem_euclid(per_second) * (1_000_000_000 / per_second);
+
+        (value.div_euclid(per_second), nanos)
+    }
+
+    pub fn format(self, value: i128) -> Option<String> {
+        let (seconds, nanos) = self.split(value);
+
+        if seconds < MIN_TIMESTAMP_SECONDS as i128 || seconds > MAX_TIMESTAMP_SECONDS as i128 {
+            return None;
+        }
+
+        Some(kind_message::format_timestamp(seconds as i64, nanos as i64))
+    }
+
+    // The plausible ranges of the units do not overlap, so at most one unit
+    // is suggested for a value.
+    pub fn suggest(value: i128) -> Option<Self> {
+        TimestampUnit::all().iter().copied().find(|unit| {
+            let (seconds, _) = unit.split(value);
+            seconds >= SUGGESTED_TIMESTAMP_SECONDS.start as i128
+                && seconds < SUGGESTED_TIMESTAMP_SECONDS.end as i128
+        })
+    }
+
+    pub fn get_value_as_string(self, value: i128) -> String {
+        match self.format(value) {
+            Some(timestamp) => timestamp,
+            None => format!("{} (not a timestamp in {})", value, self.to_string()),
+        }
+    }
+}
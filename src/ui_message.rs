@@ -1,3 +1,9 @@
+use crate::kind32::Kind32;
+use crate::kind64::Kind64;
+use crate::kind_bytes::KindBytes;
+use crate::kind_message::KindMessage;
+use crate::kind_varint::KindVarint;
+use crate::string_encoding::StringEncoding;
 use crate::web_sys::FileList;
 use uuid::Uuid;
 
@@ -14,5 +20,45 @@ pub enum UiMessage {
     ProcessUploadedFile,
     DecodeChunkAsMessage(Uuid),
     DecodeChunkAsUtf8String(Uuid),
+    DecodeChunkAsBytes(Uuid, KindBytes),
+    DecodeBytesAsChunk(Uuid),
+    DecodeStringAsBytes(Uuid, StringEncoding),
+    DecodeStringAsMessage(Uuid, StringEncoding),
     Toggle(Uuid),
+    SelectVarintKind(Uuid, KindVarint),
+    SelectKind32(Uuid, Kind32),
+    SelectKind64(Uuid, Kind64),
+    IntegerFormatChanged(Vec<u128>, String),
+    FloatFormatChanged(Vec<u128>, String),
+    SelectMessageKind(Uuid, KindMessage),
+    ToggleWireOrder,
+    ToggleGroupCollapsed(Uuid, u128),
+    ToggleGroupedActions(Uuid, u128),
+    ToggleApplyToSimilar,
+    ForgetInterpretation(Vec<u128>),
+    ViewAsTable(Vec<u128>),
+    ViewAsTree(Vec<u128>),
+    SortTable(Vec<u128>, Vec<u128>),
+    ViewAsMap(Vec<u128>),
+    ViewAsList(Vec<u128>),
+    ViewAsPlainText(Vec<u128>),
+    ViewAsEmbeddedFormat(Vec<u128>),
+    MapKeyTypeChanged(Vec<u128>, String),
+    MapValueTypeChanged(Vec<u128>, String),
+    ExportProstStructs,
+    DictionaryInputChanged(String),
+    LoadDictionary,
+    StartAnnotation(Uuid),
+    AnnotationNameChanged(String),
+    AnnotationTypeChanged(String),
+    AnnotationEnumLabelsChanged(String),
+    AnnotationBitNamesChanged(String),
+    AnnotationNoteChanged(String),
+    SaveAnnotation,
+    CancelAnnotation,
+    ExportAnnotations,
+    SchemaInputChanged(String),
+    LoadSchema,
+    RootMessageChanged(String),
+    ValidateAgainstSchema,
 }
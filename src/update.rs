@@ -1,5 +1,13 @@
 use crate::{
-    binary_proto_object_loader, data::Data, model::File, model::ProtoDec, ui_message::UiMessage,
+    binary_proto_object_loader,
+    data::Data,
+    float_format::FloatFormat,
+    integer_format::IntegerFormat,
+    map_view::MapView,
+    model::{Decoding, File, ProtoDec},
+    prost_export,
+    proto_type::ProtoType,
+    ui_message::UiMessage,
 };
 use js_sys::Uint8Array;
 use seed::prelude::*;
@@ -56,55 +64,343 @@ impl ProtoDec {
                 if let ProtoDec::InitialState(initial_state) = self {
                     if let Some(uploaded_file) = &initial_state.uploaded_file {
                         let buffer = uploaded_file.buffer.clone();
-                        *self = ProtoDec::Decoding(
+                        *self = ProtoDec::Decoding(Box::new(Decoding::new(
                             Data::Chunk {
                                 buffer,
                                 field_number: 0,
                             }
                             .into(),
-                        );
+                        )));
                     }
                 }
             }
 
             UiMessage::ProcessByteArray => {
                 if let ProtoDec::InitialState(initial_state) = self {
-                    let buffer = binary_proto_object_loader::parse_u8_vec_from_string_with_integers(
+                    match binary_proto_object_loader::parse_u8_vec_from_string(
                         initial_state.input.clone(),
-                    );
-                    if let Ok(buffer) = buffer {
-                        *self = ProtoDec::Decoding(
-                            Data::Chunk {
-                                buffer,
-                                field_number: 0,
-                            }
-                            .into(),
-                        );
+                    ) {
+                        Ok(buffer) => {
+                            *self = ProtoDec::Decoding(Box::new(Decoding::new(
+                                Data::Chunk {
+                                    buffer,
+                                    field_number: 0,
+                                }
+                                .into(),
+                            )))
+                        }
+                        Err(err) => initial_state.input_error = Some(err.message),
                     }
                 }
             }
 
             UiMessage::DecodeChunkAsMessage(uuid) => {
-                if let ProtoDec::Decoding(decoding_state) = self {
-                    decoding_state.decode_as_message(uuid);
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.decode_as_message(uuid);
                 }
             }
 
             UiMessage::Toggle(uuid) => {
-                if let ProtoDec::Decoding(decoding_state) = self {
-                    decoding_state.toggle(uuid);
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.toggle(uuid);
+                }
+            }
+
+            UiMessage::SelectVarintKind(uuid, kind) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.select_varint_kind(uuid, kind);
+                }
+            }
+
+            UiMessage::IntegerFormatChanged(path, value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    let integer_format =
+                        IntegerFormat::parse(&value).unwrap_or(IntegerFormat::Decimal);
+                    decoding.change_integer_format(&path, integer_format);
+                }
+            }
+
+            UiMessage::FloatFormatChanged(path, value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    let float_format = FloatFormat::parse(&value).unwrap_or(FloatFormat::Shortest);
+                    decoding.change_float_format(&path, float_format);
+                }
+            }
+
+            UiMessage::SelectKind32(uuid, kind) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.select_kind32(uuid, kind);
+                }
+            }
+
+            UiMessage::SelectKind64(uuid, kind) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.select_kind64(uuid, kind);
+                }
+            }
+
+            UiMessage::SelectMessageKind(uuid, kind) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.select_message_kind(uuid, kind);
+                }
+            }
+
+            UiMessage::ToggleWireOrder => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.wire_order = !decoding.wire_order;
+                }
+            }
+
+            UiMessage::ToggleApplyToSimilar => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.apply_to_similar = !decoding.apply_to_similar;
+                }
+            }
+
+            UiMessage::ForgetInterpretation(path) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.forget_interpretation(&path);
+                }
+            }
+
+            UiMessage::ToggleGroupCollapsed(uuid, field_number) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    let group = (uuid, field_number);
+                    if !decoding.collapsed_groups.remove(&group) {
+                        decoding.collapsed_groups.insert(group);
+                    }
+                }
+            }
+
+            UiMessage::ToggleGroupedActions(uuid, field_number) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    let group = (uuid, field_number);
+                    if !decoding.grouped_actions.remove(&group) {
+                        decoding.grouped_actions.insert(group);
+                    }
+                }
+            }
+
+            UiMessage::ViewAsTable(path) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.view_as_table(&path);
+                }
+            }
+
+            UiMessage::ViewAsTree(path) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.view_as_tree(&path);
+                }
+            }
+
+            UiMessage::SortTable(path, column) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.sort_table(&path, column);
+                }
+            }
+
+            UiMessage::ViewAsMap(path) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.view_as_map(&path);
+                }
+            }
+
+            UiMessage::ViewAsList(path) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.view_as_list(&path);
+                }
+            }
+
+            UiMessage::ViewAsPlainText(path) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.view_as_plain_text(&path);
+                }
+            }
+
+            UiMessage::ViewAsEmbeddedFormat(path) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.view_as_embedded_format(&path);
+                }
+            }
+
+            UiMessage::MapKeyTypeChanged(path, value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    if let (Some(map_view), Some(key_type)) =
+                        (decoding.map_views.get(&path), ProtoType::parse(&value))
+                    {
+                        let map_view = MapView {
+                            key_type,
+                            ..*map_view
+                        };
+                        decoding.change_map_types(&path, map_view);
+                    }
+                }
+            }
+
+            UiMessage::MapValueTypeChanged(path, value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    if let (Some(map_view), Some(value_type)) =
+                        (decoding.map_views.get(&path), ProtoType::parse(&value))
+                    {
+                        let map_view = MapView {
+                            value_type,
+                            ..*map_view
+                        };
+                        decoding.change_map_types(&path, map_view);
+                    }
                 }
             }
 
             UiMessage::DecodeChunkAsUtf8String(uuid) => {
-                if let ProtoDec::Decoding(decoding_state) = self {
-                    decoding_state.decode_as_utf8_string(uuid);
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.decode_as_utf8_string(uuid);
+                }
+            }
+
+            UiMessage::DecodeChunkAsBytes(uuid, kind) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.decode_as_bytes(uuid, kind);
+                }
+            }
+
+            UiMessage::DecodeBytesAsChunk(uuid) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.decode_as_chunk(uuid);
+                }
+            }
+
+            UiMessage::DecodeStringAsBytes(uuid, encoding) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.decode_string_as_bytes(uuid, encoding);
+                }
+            }
+
+            UiMessage::DecodeStringAsMessage(uuid, encoding) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.decode_string_as_message(uuid, encoding);
+                }
+            }
+
+            UiMessage::ExportProstStructs => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.exported_code = Some(prost_export::export_as_prost_structs(
+                        &decoding.root,
+                        &|path| {
+                            decoding
+                                .get_field_entry(path)
+                                .map(|entry| prost_export::FieldInfo {
+                                    name: entry.name,
+                                    enum_labels: entry.enum_labels,
+                                })
+                        },
+                    ));
+                }
+            }
+
+            UiMessage::DictionaryInputChanged(value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.dictionary_input = value
+                }
+            }
+
+            UiMessage::LoadDictionary => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.load_dictionary();
+                }
+            }
+
+            UiMessage::StartAnnotation(uuid) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.start_annotation(uuid);
+                }
+            }
+
+            UiMessage::AnnotationNameChanged(value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    if let Some(editor) = &mut decoding.annotation_editor {
+                        editor.name = value
+                    }
+                }
+            }
+
+            UiMessage::AnnotationTypeChanged(value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    if let Some(editor) = &mut decoding.annotation_editor {
+                        editor.proto_type = value
+                    }
+                }
+            }
+
+            UiMessage::AnnotationEnumLabelsChanged(value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    if let Some(editor) = &mut decoding.annotation_editor {
+                        editor.enum_labels = value
+                    }
+                }
+            }
+
+            UiMessage::AnnotationBitNamesChanged(value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    if let Some(editor) = &mut decoding.annotation_editor {
+                        editor.bit_names = value
+                    }
+                }
+            }
+
+            UiMessage::AnnotationNoteChanged(value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    if let Some(editor) = &mut decoding.annotation_editor {
+                        editor.note = value
+                    }
+                }
+            }
+
+            UiMessage::SaveAnnotation => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.save_annotation();
+                }
+            }
+
+            UiMessage::CancelAnnotation => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.annotation_editor = None;
+                }
+            }
+
+            UiMessage::ExportAnnotations => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.exported_code = Some(decoding.dictionary.to_json());
+                }
+            }
+
+            UiMessage::SchemaInputChanged(value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.schema_input = value
+                }
+            }
+
+            UiMessage::LoadSchema => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.load_schema();
+                }
+            }
+
+            UiMessage::RootMessageChanged(value) => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.change_root_message(value);
+                }
+            }
+
+            UiMessage::ValidateAgainstSchema => {
+                if let ProtoDec::Decoding(decoding) = self {
+                    decoding.validate();
                 }
             }
 
             UiMessage::InitialStateInputChanged(value) => {
                 if let ProtoDec::InitialState(initial_state) = self {
-                    initial_state.input = value
+                    initial_state.input = value;
+                    initial_state.input_error = None;
                 }
             }
         }
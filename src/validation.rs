@@ -0,0 +1,178 @@
+use crate::{
+    dictionary::format_field_path,
+    model::DecodingState,
+    schema::{MessageDescriptor, Schema},
+    wire_type::WireType,
+};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    WireTypeMismatch {
+        path: Vec<u128>,
+        field_name: String,
+        declared: String,
+        received: WireType,
+    },
+    UnknownField {
+        path: Vec<u128>,
+        message_name: String,
+    },
+    DuplicatedField {
+        path: Vec<u128>,
+        field_name: String,
+        count: usize,
+    },
+    InvalidMessage {
+        path: Vec<u128>,
+        field_name: String,
+        message_name: String,
+    },
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationIssue::WireTypeMismatch {
+                path,
+                field_name,
+                declared,
+                received,
+            } => write!(
+                f,
+                "{} {}: declared {} but received {}",
+                format_field_path(path),
+                field_name,
+                declared,
+                received.to_string()
+            ),
+            ValidationIssue::UnknownField { path, message_name } => write!(
+                f,
+                "{}: unknown field number in {}",
+                format_field_path(path),
+                message_name
+            ),
+            ValidationIssue::DuplicatedField {
+                path,
+                field_name,
+                count,
+            } => write!(
+                f,
+                "{} {}: non-repeated field occurs {} times",
+                format_field_path(path),
+                field_name,
+                count
+            ),
+            ValidationIssue::InvalidMessage {
+                path,
+                field_name,
+                message_name,
+            } => write!(
+                f,
+                "{} {}: bytes can not be decoded as {}",
+                format_field_path(path),
+                field_name,
+                message_name
+            ),
+        }
+    }
+}
+
+pub fn validate(
+    schema: &Schema,
+    root_message_name: &str,
+    root: &DecodingState,
+) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+
+    if let (Some(descriptor), Some(items)) = (
+        schema.find_message(root_message_name),
+        root.get_message_items(),
+    ) {
+        validate_message(schema, descriptor, &items, &[], &mut issues);
+    }
+
+    issues
+}
+
+fn validate_message(
+    schema: &Schema,
+    descriptor: &MessageDescriptor,
+    items: &[DecodingState],
+    path: &[u128],
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let mut occurrences: BTreeMap<u128, usize> = BTreeMap::new();
+
+    for item in items {
+        let field_number = item.get_field_number();
+        let count = occurrences.entry(field_number).or_insert(0);
+        *count += 1;
+
+        let mut item_path = path.to_vec();
+        item_path.push(field_number);
+
+        let field = match descriptor.find_field(field_number) {
+            Some(field) => field,
+            None => {
+                if *count == 1 {
+                    issues.push(ValidationIssue::UnknownField {
+                        path: item_path,
+                        message_name: descriptor.name.clone(),
+                    });
+                }
+                continue;
+            }
+        };
+
+        let declared = field.proto_type.wire_type();
+        let received = item.get_wire_type();
+        let packed = field.repeated
+            && declared != WireType::LengthDelimited
+            && received == WireType::LengthDelimited;
+
+        if declared != received && !packed {
+            issues.push(ValidationIssue::WireTypeMismatch {
+                path: item_path,
+                field_name: field.name.clone(),
+                declared: field.proto_type.to_string().into(),
+                received,
+            });
+            continue;
+        }
+
+        let nested_descriptor = field
+            .type_name
+            .as_ref()
+            .and_then(|type_name| schema.find_message(type_name));
+
+        if let Some(nested_descriptor) = nested_descriptor {
+            match item.get_message_items() {
+                Some(items) => {
+                    validate_message(schema, nested_descriptor, &items, &item_path, issues)
+                }
+                None => issues.push(ValidationIssue::InvalidMessage {
+                    path: item_path,
+                    field_name: field.name.clone(),
+                    message_name: nested_descriptor.name.clone(),
+                }),
+            }
+        }
+    }
+
+    for (field_number, count) in occurrences {
+        if let Some(field) = descriptor.find_field(field_number) {
+            if count > 1 && !field.repeated {
+                let mut item_path = path.to_vec();
+                item_path.push(field_number);
+
+                issues.push(ValidationIssue::DuplicatedField {
+                    path: item_path,
+                    field_name: field.name.clone(),
+                    count,
+                });
+            }
+        }
+    }
+}
@@ -1,17 +1,38 @@
-pub fn take_varint(buffer: &[u8]) -> (u128, &[u8]) {
+use crate::error::ProtodecError;
+
+// Ten bytes hold every 64 bit value, which is the widest varint protobuf
+// writes.
+const MAX_VARINT_LENGTH: usize = 10;
+
+pub fn take_varint(buffer: &[u8]) -> Result<(u128, &[u8]), ProtodecError> {
+    if buffer.is_empty() {
+        return Err(ProtodecError {
+            message: "Error. Buffer can't be empty here.".into(),
+        });
+    }
+
     let mut shifter = 0;
     let mut value: u128 = 0;
 
-    let mut index_to_skip = 0;
-    for (index, byte) in buffer.iter().enumerate() {
-        index_to_skip = index;
+    for (index, byte) in buffer.iter().enumerate().take(MAX_VARINT_LENGTH) {
         value |= (*byte as u128 & 0x7F) << shifter;
         shifter += 7;
 
         if (byte & 0x80) == 0 {
-            break;
+            return Ok((value, &buffer[index + 1..]));
         }
     }
 
-    (value, &buffer[index_to_skip + 1..])
+    if buffer.len() > MAX_VARINT_LENGTH {
+        Err(ProtodecError {
+            message: format!(
+                "Error. Varint can't be longer than {} bytes.",
+                MAX_VARINT_LENGTH
+            ),
+        })
+    } else {
+        Err(ProtodecError {
+            message: "Error. Varint is not terminated.".into(),
+        })
+    }
 }
@@ -1,9 +1,28 @@
 use crate::{
+    dictionary::format_field_path,
+    document::DocumentValue,
+    embedded_format::{self, EmbeddedFormat},
+    entropy,
+    file_format::FileFormat,
+    float_format::FloatFormat,
     format,
-    model::{DecodingState, InitialState, ProtoDec},
+    integer_format::IntegerFormat,
+    kind32::Kind32,
+    kind64::Kind64,
+    kind_bytes::KindBytes,
+    kind_message::{self, KindMessage},
+    kind_varint::KindVarint,
+    map_view::{self, MapView},
+    model::{AnnotationEditor, Decoding, DecodingState, InitialState, ProtoDec, SchemaScope},
+    proto_type::ProtoType,
+    string_encoding::StringEncoding,
+    table_view::{Table, TableView},
+    text_encoding::{self, TextSegment},
+    timestamp_unit::TimestampUnit,
     ui_message::UiMessage,
 };
 use seed::{prelude::*, *};
+use uuid::Uuid;
 use web_sys::{self};
 
 macro_rules! stop_and_prevent {
@@ -24,7 +43,7 @@ impl ProtoDec {
             },
             match self {
                 ProtoDec::InitialState(initial_state) => view_initial_state(initial_state),
-                ProtoDec::Decoding(decoding_state) => view_decoding_state(decoding_state)
+                ProtoDec::Decoding(decoding) => view_decoding(decoding)
             },
             footer![
                 C!{"im_footer"},
@@ -49,7 +68,7 @@ impl ProtoDec {
 
 pub fn view_initial_state(initial_state: &InitialState) -> Node<UiMessage> {
     div![
-        label!["Put array of bytes as integers, for example: 10, 8, 74, 111, 104, 110, 32, 68, 111, 101, 16, 30"],
+        label!["Put array of bytes as integers, hex, base64 or a byte literal from code, for example: 10, 8, 74, 111, 104, 110, 32, 68, 111, 101, 16, 30 or 0a 08 4a 6f 68 6e 20 44 6f 65 10 1e"],
         textarea! {
             attrs! {
                 At::Value => initial_state.input,
@@ -62,6 +81,11 @@ pub fn view_initial_state(initial_state: &InitialState) -> Node<UiMessage> {
         },
         br![],
         button! {"Open bytes array", ev(Ev::Click, |_| UiMessage::ProcessByteArray),},
+        if let Some(error) = &initial_state.input_error {
+            p![error]
+        } else {
+            empty()
+        },
         br![],
         br![],
         label!["Or in case when you have the binary file:"],
@@ -113,28 +137,318 @@ fn view_drop_zone(initial_state: &InitialState) -> Node<UiMessage> {
     ]
 }
 
-pub fn view_decoding_state(decoding_state: &DecodingState) -> Node<UiMessage> {
-    view_decoding_state_recoursive(decoding_state)
+pub fn view_decoding(decoding: &Decoding) -> Node<UiMessage> {
+    div![
+        view_decoding_toolbar(decoding),
+        view_dictionary_input(decoding),
+        view_schema_input(decoding),
+        view_validation_report(decoding),
+        view_decoding_state(decoding),
+        view_exported_code(decoding),
+    ]
+}
+
+fn view_decoding_toolbar(decoding: &Decoding) -> Node<UiMessage> {
+    div![
+        button! {
+            C!["im_button_size"],
+            if decoding.wire_order { "Group repeated fields" } else { "Show in wire order" },
+            ev(Ev::Click, |_| UiMessage::ToggleWireOrder)
+        },
+        label![
+            C!["im_inline_block"],
+            input! {
+                attrs! {
+                    At::Type => "checkbox",
+                    At::Checked => decoding.apply_to_similar.as_at_value(),
+                },
+                ev(Ev::Change, |_| UiMessage::ToggleApplyToSimilar)
+            },
+            "Apply to all similar"
+        ],
+        button! {
+            C!["im_button_size"],
+            "Export prost structs",
+            ev(Ev::Click, |_| UiMessage::ExportProstStructs)
+        },
+        button! {
+            C!["im_button_size"],
+            "Export annotations",
+            ev(Ev::Click, |_| UiMessage::ExportAnnotations)
+        },
+        view_remembered_interpretations(decoding),
+    ]
+}
+
+fn view_remembered_interpretations(decoding: &Decoding) -> Node<UiMessage> {
+    if decoding.remembered_interpretations.is_empty() {
+        return empty();
+    }
+
+    ul![
+        C!["im_list_style_none"],
+        decoding
+            .remembered_interpretations
+            .iter()
+            .map(|(path, interpretation)| {
+                let path = path.clone();
+                li![
+                    div![
+                        C!["im_inline_block"],
+                        format!(
+                            "{} as {}",
+                            format_field_path(&path),
+                            interpretation.get_description()
+                        )
+                    ],
+                    button! {
+                        C!["im_button_size"],
+                        "Forget",
+                        ev(Ev::Click, move |_| UiMessage::ForgetInterpretation(path))
+                    },
+                ]
+            })
+    ]
+}
+
+fn view_dictionary_input(decoding: &Decoding) -> Node<UiMessage> {
+    div![
+        label![
+            r#"Field dictionary, for example: { "1": "name: string", "4.2": { "name": "type", "type": "enum", "enum": { "2": "WORK" } } }"#
+        ],
+        textarea! {
+            attrs! {
+                At::Value => decoding.dictionary_input,
+                At::Rows => 3,
+            },
+            style![
+                St::Width => "100%",
+            ],
+            input_ev(Ev::Input, UiMessage::DictionaryInputChanged)
+        },
+        button! {"Load dictionary", ev(Ev::Click, |_| UiMessage::LoadDictionary),},
+        if let Some(dictionary_error) = &decoding.dictionary_error {
+            p![dictionary_error]
+        } else {
+            empty()
+        },
+    ]
+}
+
+fn view_schema_input(decoding: &Decoding) -> Node<UiMessage> {
+    div![
+        label!["Schema, the content of a .proto file:"],
+        textarea! {
+            attrs! {
+                At::Value => decoding.schema_input,
+                At::Rows => 3,
+            },
+            style![
+                St::Width => "100%",
+            ],
+            input_ev(Ev::Input, UiMessage::SchemaInputChanged)
+        },
+        button! {"Load schema", ev(Ev::Click, |_| UiMessage::LoadSchema),},
+        if let Some(schema) = &decoding.schema {
+            let message_options = schema.messages.iter().map(|message| {
+                option! {
+                    attrs! {
+                        At::Value => message.name,
+                        At::Selected => (message.name == decoding.root_message_name).as_at_value(),
+                    },
+                    &message.name
+                }
+            });
+
+            div![
+                C!["im_inline_block"],
+                select! {
+                    C!["im_button_size"],
+                    message_options,
+                    input_ev(Ev::Change, UiMessage::RootMessageChanged)
+                },
+                button! {
+                    C!["im_button_size"],
+                    "Validate",
+                    ev(Ev::Click, |_| UiMessage::ValidateAgainstSchema)
+                },
+            ]
+        } else {
+            empty()
+        },
+        if let Some(schema_error) = &decoding.schema_error {
+            p![schema_error]
+        } else {
+            empty()
+        },
+    ]
+}
+
+fn view_validation_report(decoding: &Decoding) -> Node<UiMessage> {
+    match &decoding.validation_report {
+        Some(issues) if issues.is_empty() => p!["The message matches the schema."],
+        Some(issues) => ul![issues.iter().map(|issue| li![issue.to_string()])],
+        None => empty(),
+    }
+}
+
+fn view_exported_code(decoding: &Decoding) -> Node<UiMessage> {
+    if let Some(exported_code) = &decoding.exported_code {
+        pre! {exported_code}
+    } else {
+        empty()
+    }
+}
+
+pub fn view_decoding_state(decoding: &Decoding) -> Node<UiMessage> {
+    view_decoding_state_recoursive(decoding, &decoding.root, &[], decoding.get_root_scope())
 }
 
-pub fn view_decoding_state_recoursive(decoding_state: &DecodingState) -> Node<UiMessage> {
-    let field_number = decoding_state.get_formatted_field_number();
+pub fn view_decoding_state_recoursive(
+    decoding: &Decoding,
+    decoding_state: &DecodingState,
+    path: &[u128],
+    scope: SchemaScope,
+) -> Node<UiMessage> {
+    let field_entry = decoding.get_field_entry_in(scope, path);
+    let field_number = decoding_state.get_formatted_field_number(field_entry.as_ref());
 
-    let kind = decoding_state.get_formatted_kind();
+    let kind = decoding_state.get_formatted_kind(field_entry.as_ref());
     div![
         div![
             div![C!["im_inline_block"], format!("{} {}", field_number, kind)],
-            div![C!["im_inline_block"], view_action_buttons(decoding_state)],
+            div![
+                C!["im_inline_block"],
+                view_action_buttons(decoding, decoding_state, path, scope)
+            ],
+            div![
+                C!["im_inline_block"],
+                view_annotate_button(decoding_state, path)
+            ],
         ],
-        div![view_value(decoding_state)]
+        match field_entry.as_ref().and_then(|entry| entry.note.as_ref()) {
+            Some(note) => div![C!["im_annotation_note"], note],
+            None => empty(),
+        },
+        match &decoding.annotation_editor {
+            Some(editor) if editor.uuid == decoding_state.get_uuid() => {
+                view_annotation_editor(editor)
+            }
+            _ => empty(),
+        },
+        div![view_value(decoding, decoding_state, path, scope)]
     ]
 }
 
-pub fn view_value(decoding_state: &DecodingState) -> Node<UiMessage> {
-    match decoding_state {
-        DecodingState::Varint { value, kind, .. } => {
-            pre! {kind.get_value_as_string(*value)}
+fn view_annotate_button(decoding_state: &DecodingState, path: &[u128]) -> Node<UiMessage> {
+    if path.is_empty() {
+        return empty();
+    }
+
+    let uuid = decoding_state.get_uuid();
+    button! {C!["im_button_size"], "Annotate", ev(Ev::Click, move |_| UiMessage::StartAnnotation(uuid))}
+}
+
+fn view_annotation_editor(editor: &AnnotationEditor) -> Node<UiMessage> {
+    let type_options = ProtoType::all().iter().map(|proto_type| {
+        let proto_type = proto_type.to_string();
+        option! {
+            attrs! {
+                At::Value => proto_type,
+                At::Selected => (proto_type == editor.proto_type).as_at_value(),
+            },
+            proto_type
         }
+    });
+
+    div![
+        C!["im_annotation_editor"],
+        input! {
+            attrs! {
+                At::Value => editor.name,
+                At::Placeholder => "Name",
+            },
+            input_ev(Ev::Input, UiMessage::AnnotationNameChanged)
+        },
+        select! {
+            option! {
+                attrs! {
+                    At::Value => "",
+                    At::Selected => editor.proto_type.is_empty().as_at_value(),
+                },
+                "unknown type"
+            },
+            type_options,
+            input_ev(Ev::Change, UiMessage::AnnotationTypeChanged)
+        },
+        input! {
+            attrs! {
+                At::Value => editor.enum_labels,
+                At::Placeholder => "Enum labels, for example: 0=MOBILE, 2=WORK",
+            },
+            input_ev(Ev::Input, UiMessage::AnnotationEnumLabelsChanged)
+        },
+        input! {
+            attrs! {
+                At::Value => editor.bit_names,
+                At::Placeholder => "Flag bit names, for example: 0=READ, 1=WRITE",
+            },
+            input_ev(Ev::Input, UiMessage::AnnotationBitNamesChanged)
+        },
+        textarea! {
+            attrs! {
+                At::Value => editor.note,
+                At::Placeholder => "Note",
+                At::Rows => 2,
+            },
+            style![
+                St::Width => "100%",
+            ],
+            input_ev(Ev::Input, UiMessage::AnnotationNoteChanged)
+        },
+        button! {C!["im_button_size"], "Save", ev(Ev::Click, |_| UiMessage::SaveAnnotation)},
+        button! {C!["im_button_size"], "Cancel", ev(Ev::Click, |_| UiMessage::CancelAnnotation)},
+        if let Some(error) = &editor.error {
+            p![error]
+        } else {
+            empty()
+        },
+    ]
+}
+
+pub fn view_value(
+    decoding: &Decoding,
+    decoding_state: &DecodingState,
+    path: &[u128],
+    scope: SchemaScope,
+) -> Node<UiMessage> {
+    if let Some(value) = decoding
+        .get_formatted_integer(decoding_state, scope, path)
+        .or_else(|| decoding.get_formatted_float(decoding_state, path))
+    {
+        return pre! {value};
+    }
+
+    match decoding_state {
+        DecodingState::Varint { value, kind, .. } => match kind {
+            KindVarint::Enum => {
+                let enum_labels = decoding
+                    .get_field_entry_in(scope, path)
+                    .map(|entry| entry.enum_labels)
+                    .unwrap_or_default();
+
+                pre! {KindVarint::get_value_as_enum_label(*value, &enum_labels)}
+            }
+            _ if !kind.is_in_range(*value) => pre! {
+                C!["im_out_of_range"],
+                format!(
+                    "{} (out of range for {})",
+                    kind.get_value_as_string(*value),
+                    kind.to_string()
+                )
+            },
+            _ => pre! {kind.get_value_as_string(*value)},
+        },
 
         DecodingState::Fixed64 { buffer, kind, .. } => {
             pre! {kind.get_value_as_string(buffer)}
@@ -145,61 +459,890 @@ pub fn view_value(decoding_state: &DecodingState) -> Node<UiMessage> {
         }
 
         DecodingState::Utf8String { value, .. } => {
-            pre! {value}
+            match decoding.get_embedded_format(decoding_state, path) {
+                Some(embedded_format) => view_embedded_format(value, embedded_format),
+                None => pre! {value},
+            }
         }
 
-        DecodingState::Chunk { buffer, .. } => {
-            pre! {format::format_as_ascii_and_hex(buffer)}
+        DecodingState::Chunk {
+            buffer,
+            field_number,
+            ..
+        } => match FileFormat::detect(buffer) {
+            Some(file_format) => div![
+                view_file_preview(file_format, buffer, *field_number),
+                pre! {format::format_as_ascii_and_hex(buffer)},
+            ],
+            None => pre! {format::format_as_ascii_and_hex(buffer)},
+        },
+
+        DecodingState::Bytes {
+            buffer,
+            kind: KindBytes::Utf8Lossy,
+            ..
+        } => view_utf8_lossy(buffer),
+
+        DecodingState::Bytes { buffer, kind, .. } => match kind.decode_document(buffer) {
+            Some(Ok(value)) => view_document_value(None, &value),
+            Some(Err(err)) => div![
+                p![format!("Not valid {}: {}", kind.to_string(), err.message)],
+                pre! {format::format_as_ascii_and_hex(buffer)},
+            ],
+            None => pre! {kind.get_value_as_string(buffer)},
+        },
+
+        DecodingState::Message { items, kind, .. } => {
+            if let Some(value) = kind.get_value_as_string(items) {
+                return pre! {value};
+            }
+
+            if *kind == KindMessage::Any {
+                if let Some(type_url) = kind_message::get_any_type_url(items) {
+                    return view_any(decoding, items, path, scope, &type_url);
+                }
+            }
+
+            let parent_uuid = decoding_state.get_uuid();
+            let mut grouped_field_numbers = vec![];
+            let list_items = items.iter().filter_map(|item| {
+                let field_number = item.get_field_number();
+                let mut item_path = path.to_vec();
+                item_path.push(field_number);
+
+                let count = items
+                    .iter()
+                    .filter(|other| other.get_field_number() == field_number)
+                    .count();
+                let map_view = decoding.map_views.get(&item_path);
+
+                if map_view.is_none() && (count == 1 || decoding.wire_order) {
+                    return Some(li! {
+                        view_decoding_state_recoursive(decoding, item, &item_path, scope)
+                    });
+                }
+
+                if grouped_field_numbers.contains(&field_number) {
+                    return None;
+                }
+                grouped_field_numbers.push(field_number);
+
+                Some(li! {match map_view {
+                    Some(map_view) => view_map(decoding, items, &item_path, scope, *map_view),
+                    None => view_repeated_group(decoding, parent_uuid, items, &item_path, scope),
+                }})
+            });
+
+            div![
+                if *kind == KindMessage::Message {
+                    empty()
+                } else {
+                    p![format!("The fields don't match {}.", kind.to_string())]
+                },
+                ul![C!["im_list_style_none"], list_items]
+            ]
         }
+    }
+}
 
-        DecodingState::Message { items, .. } => {
-            let items = items
-                .iter()
-                .map(|item| li! {view_decoding_state_recoursive(item)});
+fn view_repeated_group(
+    decoding: &Decoding,
+    parent_uuid: Uuid,
+    items: &[DecodingState],
+    path: &[u128],
+    scope: SchemaScope,
+) -> Node<UiMessage> {
+    let field_number = path[path.len() - 1];
+    let elements: Vec<&DecodingState> = items
+        .iter()
+        .filter(|item| item.get_field_number() == field_number)
+        .collect();
+
+    let group = (parent_uuid, field_number);
+    let collapsed = decoding.collapsed_groups.contains(&group);
+    let grouped_actions = decoding.grouped_actions.contains(&group);
+
+    let field_entry = decoding.get_field_entry_in(scope, path);
+    let name = elements[0].get_formatted_field_number(field_entry.as_ref());
+
+    let table_view = decoding.table_views.get(path);
+    let table_path = path.to_vec();
+    let table_button = if table_view.is_some() {
+        button! {
+            C!["im_button_size"],
+            "View as tree",
+            ev(Ev::Click, move |_| UiMessage::ViewAsTree(table_path))
+        }
+    } else if Table::fits(&elements) {
+        button! {
+            C!["im_button_size"],
+            "View as table",
+            ev(Ev::Click, move |_| UiMessage::ViewAsTable(table_path))
+        }
+    } else {
+        empty()
+    };
+
+    div![
+        div![
+            button! {
+                C!["im_button_size"],
+                if collapsed { "▸" } else { "▾" },
+                ev(Ev::Click, move |_| UiMessage::ToggleGroupCollapsed(parent_uuid, field_number))
+            },
+            div![
+                C!["im_inline_block"],
+                format!("repeated field {} (×{})", name.trim_end(), elements.len())
+            ],
+            label![
+                C!["im_inline_block"],
+                input! {
+                    attrs! {
+                        At::Type => "checkbox",
+                        At::Checked => grouped_actions.as_at_value(),
+                    },
+                    ev(Ev::Change, move |_| UiMessage::ToggleGroupedActions(parent_uuid, field_number))
+                },
+                "Apply actions to all"
+            ],
+            table_button,
+        ],
+        if collapsed {
+            empty()
+        } else if let Some(table_view) = table_view {
+            view_table(decoding, &elements, path, scope, table_view)
+        } else {
+            ul![
+                C!["im_list_style_none"],
+                elements.iter().map(|element| {
+                    li! {view_decoding_state_recoursive(decoding, element, path, scope)}
+                })
+            ]
+        },
+    ]
+}
+
+fn view_table(
+    decoding: &Decoding,
+    elements: &[&DecodingState],
+    path: &[u128],
+    scope: SchemaScope,
+    table_view: &TableView,
+) -> Node<UiMessage> {
+    let mut table = Table::from_elements(elements, |state, column| {
+        let mut cell_path = path.to_vec();
+        cell_path.extend_from_slice(column);
+        format_table_cell(decoding, state, &cell_path, scope)
+    });
+    table.sort(table_view);
+
+    let column_titles: Vec<String> = table
+        .columns
+        .iter()
+        .map(|column| {
+            let mut column_path = path.to_vec();
+            column_path.extend_from_slice(column);
+
+            match decoding
+                .get_field_entry_in(scope, &column_path)
+                .and_then(|entry| entry.name)
+            {
+                Some(name) => format!("{} ({})", name, format_field_path(column)),
+                None => format_field_path(column),
+            }
+        })
+        .collect();
+
+    let csv = table.to_csv(&column_titles);
+    let csv_href = format!(
+        "data:text/csv;charset=utf-8,{}",
+        String::from(js_sys::encode_uri_component(&csv))
+    );
+
+    let header = table
+        .columns
+        .iter()
+        .zip(column_titles.iter())
+        .map(|(column, title)| {
+            let sort_mark = match &table_view.sort_column {
+                Some(sort_column) if sort_column == column => {
+                    if table_view.descending {
+                        " ▼"
+                    } else {
+                        " ▲"
+                    }
+                }
+                _ => "",
+            };
+
+            let table_path = path.to_vec();
+            let column = column.clone();
+            th![
+                format!("{}{}", title, sort_mark),
+                ev(Ev::Click, move |_| UiMessage::SortTable(table_path, column))
+            ]
+        });
+
+    let rows = table
+        .rows
+        .iter()
+        .map(|row| tr![row.iter().map(|cell| td![cell])]);
+
+    div![
+        a! {
+            attrs! {
+                At::Href => csv_href,
+                At::Download => format!("field_{}.csv", format_field_path(path)),
+            },
+            "Export CSV"
+        },
+        table![C!["im_table"], tr![header], rows],
+    ]
+}
+
+fn format_table_cell(
+    decoding: &Decoding,
+    state: &DecodingState,
+    path: &[u128],
+    scope: SchemaScope,
+) -> String {
+    if let Some(value) = decoding
+        .get_formatted_integer(state, scope, path)
+        .or_else(|| decoding.get_formatted_float(state, path))
+    {
+        return value;
+    }
+
+    match state {
+        DecodingState::Varint {
+            value,
+            kind: KindVarint::Enum,
+            ..
+        } => {
+            let enum_labels = decoding
+                .get_field_entry_in(scope, path)
+                .map(|entry| entry.enum_labels)
+                .unwrap_or_default();
+            KindVarint::get_value_as_enum_label(*value, &enum_labels)
+        }
+        DecodingState::Utf8String { value, .. } => value.clone(),
+        DecodingState::Message { items, kind, .. } => {
+            kind.get_value_as_string(items).unwrap_or_default()
+        }
+        _ => state.get_scalar_value_as_string().unwrap_or_default(),
+    }
+}
+
+fn view_map(
+    decoding: &Decoding,
+    items: &[DecodingState],
+    path: &[u128],
+    scope: SchemaScope,
+    map_view: MapView,
+) -> Node<UiMessage> {
+    let field_number = path[path.len() - 1];
+    let entries: Vec<&DecodingState> = items
+        .iter()
+        .filter(|item| item.get_field_number() == field_number)
+        .collect();
+
+    let field_entry = decoding.get_field_entry_in(scope, path);
+    let name = match field_entry.as_ref().and_then(|entry| entry.name.as_ref()) {
+        Some(name) => format!("{} ({})", name, field_number),
+        None => format!("{:<2?}", field_number),
+    };
+
+    let key_path = path.to_vec();
+    let value_path = path.to_vec();
+    let list_path = path.to_vec();
+
+    let rows = entries.iter().map(|entry| {
+        let entry_items = entry.get_message_items().unwrap_or_default();
+        let key = map_view::find_field(&entry_items, 1)
+            .and_then(|key| key.get_scalar_value_as_string())
+            .unwrap_or_else(|| "(default)".to_string());
+
+        let mut value_path = path.to_vec();
+        value_path.push(2);
+
+        let value = match (entry, map_view::find_field(&entry_items, 2)) {
+            (_, None) => pre! {"(default)"},
+            (DecodingState::Message { items, .. }, Some(value)) => {
+                match value.get_scalar_value_as_string() {
+                    Some(value) => pre! {value},
+                    None => {
+                        let value = map_view::find_field(items, 2).unwrap_or(value);
+                        view_value(decoding, value, &value_path, scope)
+                    }
+                }
+            }
+            (_, Some(value)) => pre! {value.get_scalar_value_as_string().unwrap_or_default()},
+        };
+
+        tr![td![pre! {key}], td!["→"], td![value]]
+    });
+
+    div![
+        div![
+            div![
+                C!["im_inline_block"],
+                format!(
+                    "{} <map<{}, {}>> (×{})",
+                    name,
+                    map_view.key_type.to_string(),
+                    map_view.value_type.to_string(),
+                    entries.len()
+                )
+            ],
+            div![
+                C!["im_inline_block"],
+                view_proto_type_select(
+                    map_view.key_type,
+                    map_view::is_valid_key_type,
+                    move |value| UiMessage::MapKeyTypeChanged(key_path.clone(), value)
+                ),
+                view_proto_type_select(
+                    map_view.value_type,
+                    |_| true,
+                    move |value| UiMessage::MapValueTypeChanged(value_path.clone(), value)
+                ),
+                button! {
+                    C!["im_button_size"],
+                    "View as list",
+                    ev(Ev::Click, move |_| UiMessage::ViewAsList(list_path))
+                },
+            ],
+        ],
+        table![C!["im_map"], rows],
+    ]
+}
+
+fn view_proto_type_select(
+    selected: ProtoType,
+    filter: impl Fn(ProtoType) -> bool,
+    handler: impl FnOnce(String) -> UiMessage + 'static + Clone,
+) -> Node<UiMessage> {
+    let type_options = ProtoType::all()
+        .iter()
+        .filter(|proto_type| filter(**proto_type))
+        .map(|proto_type| {
+            option! {
+                attrs! {
+                    At::Value => proto_type.to_string(),
+                    At::Selected => (*proto_type == selected).as_at_value(),
+                },
+                proto_type.to_string()
+            }
+        });
+
+    select! {
+        C!["im_button_size"],
+        type_options,
+        input_ev(Ev::Change, handler)
+    }
+}
 
-            ul![C!["im_list_style_none"], items]
+fn view_any(
+    decoding: &Decoding,
+    items: &[DecodingState],
+    path: &[u128],
+    scope: SchemaScope,
+    type_url: &str,
+) -> Node<UiMessage> {
+    let type_name = kind_message::get_any_type_name(type_url);
+    let payload_scope = match &decoding.schema {
+        Some(schema) if schema.find_message(type_name).is_some() => SchemaScope {
+            message_name: type_name,
+            path_offset: path.len() + 1,
+        },
+        _ => scope,
+    };
+
+    let payload = items
+        .iter()
+        .filter(|item| item.get_field_number() == 2)
+        .map(|item| {
+            let mut item_path = path.to_vec();
+            item_path.push(item.get_field_number());
+            li! {view_decoding_state_recoursive(decoding, item, &item_path, payload_scope)}
+        });
+
+    div![
+        div![C!["im_any_type_url"], type_url],
+        ul![C!["im_list_style_none"], payload]
+    ]
+}
+
+fn view_kind_select<K: Copy + PartialEq>(
+    kinds: &[K],
+    kind: K,
+    to_string: fn(K) -> &'static str,
+    on_change: impl FnOnce(String) -> UiMessage + Clone + 'static,
+) -> Node<UiMessage> {
+    let kind_options = kinds.iter().map(|option_kind| {
+        option! {
+            attrs! {
+                At::Value => to_string(*option_kind),
+                At::Selected => (*option_kind == kind).as_at_value(),
+            },
+            to_string(*option_kind)
         }
+    });
+
+    select! {
+        C!["im_button_size"],
+        kind_options,
+        input_ev(Ev::Change, on_change)
     }
 }
 
-pub fn view_action_buttons(decoding_state: &DecodingState) -> Node<UiMessage> {
+fn view_utf8_lossy(buffer: &[u8]) -> Node<UiMessage> {
+    let segments = text_encoding::decode_utf8_lossy(buffer);
+
+    pre![segments.into_iter().map(|segment| match segment {
+        TextSegment::Text(text) => span![text],
+        TextSegment::Invalid { offset, bytes } => span! {
+            C!["im_invalid_bytes"],
+            attrs! {
+                At::Title => format!("invalid UTF-8 at byte {}", offset),
+            },
+            format!("[{}: {}]", offset, format::format_as_hex(&bytes))
+        },
+    })]
+}
+
+fn view_embedded_format(value: &str, embedded_format: EmbeddedFormat) -> Node<UiMessage> {
+    match embedded_format {
+        EmbeddedFormat::Json => match serde_json::from_str(value.trim()) {
+            Ok(json) => div! {C!["im_json"], view_json_value(None, &json)},
+            Err(_) => pre! {value},
+        },
+        EmbeddedFormat::Xml => pre! {embedded_format::format_xml(value.trim()).unwrap_or_default()},
+        EmbeddedFormat::UrlEncoded => table! {
+            embedded_format::parse_url_encoded(value.trim())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(key, value)| tr! {td!{key}, td!{pre!{value}}})
+        },
+    }
+}
+
+fn view_json_value(key: Option<String>, json: &serde_json::Value) -> Node<UiMessage> {
+    let prefix = key.map(|key| format!("{}: ", key)).unwrap_or_default();
+
+    match json {
+        serde_json::Value::Object(object) => details! {
+            attrs! {At::Open => AtValue::None},
+            summary! {format!("{}{{…}} {} keys", prefix, object.len())},
+            object
+                .iter()
+                .map(|(key, value)| view_json_value(Some(format!("{:?}", key)), value)),
+        },
+        serde_json::Value::Array(array) => details! {
+            attrs! {At::Open => AtValue::None},
+            summary! {format!("{}[…] {} items", prefix, array.len())},
+            array
+                .iter()
+                .enumerate()
+                .map(|(index, value)| view_json_value(Some(index.to_string()), value)),
+        },
+        _ => div! {format!("{}{}", prefix, json)},
+    }
+}
+
+// Follows the layout of the protobuf tree: a key and a type on one line,
+// with the value or the nested items below.
+fn view_document_value(key: Option<String>, value: &DocumentValue) -> Node<UiMessage> {
+    let header = match key {
+        Some(key) => format!("{} <{}>", key, value.get_type_name()),
+        None => format!("<{}>", value.get_type_name()),
+    };
+
+    match value {
+        DocumentValue::Array(items) => div![
+            div![header],
+            ul![
+                C!["im_list_style_none"],
+                items.iter().enumerate().map(|(index, item)| li! {
+                    view_document_value(Some(index.to_string()), item)
+                })
+            ]
+        ],
+        DocumentValue::Map(entries) => div![
+            div![header],
+            ul![
+                C!["im_list_style_none"],
+                entries.iter().map(|(key, value)| li! {
+                    view_document_value(Some(key.format()), value)
+                })
+            ]
+        ],
+        DocumentValue::Tagged(_, inner) => div![
+            div![header],
+            ul![
+                C!["im_list_style_none"],
+                li! {view_document_value(None, inner)}
+            ]
+        ],
+        _ => div![div![header], pre! {value.format()}],
+    }
+}
+
+fn view_file_preview(
+    file_format: FileFormat,
+    buffer: &[u8],
+    field_number: u128,
+) -> Node<UiMessage> {
+    let data_url = file_format.get_data_url(buffer);
+
+    div![
+        C!["im_file_preview"],
+        span![format!(
+            "{}, {} bytes",
+            file_format.to_string(),
+            buffer.len()
+        )],
+        a! {
+            C!["im_button_size"],
+            attrs! {
+                At::Href => data_url,
+                At::Download => format!("field_{}.{}", field_number, file_format.get_extension()),
+            },
+            "Download"
+        },
+        if file_format.is_image() {
+            img! {attrs! {At::Src => data_url, At::Alt => file_format.to_string()}}
+        } else {
+            empty()
+        },
+    ]
+}
+
+fn view_entropy(buffer: &[u8]) -> Node<UiMessage> {
+    if buffer.is_empty() {
+        return empty();
+    }
+
+    let high_entropy = entropy::is_high_entropy(buffer);
+    let histogram = entropy::get_histogram(buffer);
+    let max_count = histogram.iter().copied().max().unwrap_or(1);
+
+    span![
+        C!["im_entropy"],
+        span![
+            C!["im_entropy_badge", IF!(high_entropy => "im_high_entropy")],
+            attrs! {
+                At::Title => if high_entropy {
+                    "Likely encrypted, compressed or random"
+                } else {
+                    "Shannon entropy in bits per byte"
+                },
+            },
+            format!("entropy {:.2}", entropy::get_entropy(buffer))
+        ],
+        span![
+            C!["im_histogram"],
+            histogram.iter().enumerate().map(|(index, count)| span![
+                attrs! {
+                    At::Title => format!("{:02x}-{:02x}: {}", index * 16, index * 16 + 15, count),
+                },
+                style! {St::Height => px(1 + 15 * count / max_count)},
+            ])
+        ],
+    ]
+}
+
+fn get_fitting_bytes_kinds(buffer: &[u8]) -> Vec<KindBytes> {
+    KindBytes::all()
+        .iter()
+        .copied()
+        .filter(|kind| kind.fits(buffer))
+        .collect()
+}
+
+fn view_number_format_select(
+    decoding: &Decoding,
+    decoding_state: &DecodingState,
+    path: &[u128],
+) -> Node<UiMessage> {
+    if decoding_state.get_float_bits().is_some() {
+        return view_float_format_select(decoding, path);
+    }
+
+    if decoding_state.get_integer_bits().is_none() {
+        return empty();
+    }
+
+    let integer_format = decoding
+        .integer_formats
+        .get(path)
+        .copied()
+        .unwrap_or(IntegerFormat::Decimal);
+    let path = path.to_vec();
+
+    view_kind_select(
+        IntegerFormat::all(),
+        integer_format,
+        IntegerFormat::to_string,
+        move |value| UiMessage::IntegerFormatChanged(path, value),
+    )
+}
+
+fn view_float_format_select(decoding: &Decoding, path: &[u128]) -> Node<UiMessage> {
+    let float_format = decoding
+        .float_formats
+        .get(path)
+        .copied()
+        .unwrap_or(FloatFormat::Shortest);
+    let path = path.to_vec();
+
+    view_kind_select(
+        FloatFormat::all(),
+        float_format,
+        FloatFormat::to_string,
+        move |value| UiMessage::FloatFormatChanged(path, value),
+    )
+}
+
+fn view_timestamp_suggestion(unit: TimestampUnit, ui_message: UiMessage) -> Node<UiMessage> {
+    button! {
+        C!["im_button_size"],
+        format!("As timestamp {}?", unit.to_string()),
+        ev(Ev::Click, move |_| ui_message)
+    }
+}
+
+fn view_message_kind_select(
+    decoding: &Decoding,
+    decoding_state: &DecodingState,
+    path: &[u128],
+    scope: SchemaScope,
+) -> Node<UiMessage> {
+    let (uuid, items, kind) = match decoding_state {
+        DecodingState::Message {
+            uuid, items, kind, ..
+        } => (*uuid, items, *kind),
+        _ => return empty(),
+    };
+
+    let kind_options = KindMessage::all().iter().map(|message_kind| {
+        option! {
+            attrs! {
+                At::Value => message_kind.to_string(),
+                At::Selected => (*message_kind == kind).as_at_value(),
+            },
+            message_kind.to_string()
+        }
+    });
+
+    div![
+        C!["im_inline_block"],
+        select! {
+            C!["im_button_size"],
+            kind_options,
+            input_ev(Ev::Change, move |value| {
+                UiMessage::SelectMessageKind(
+                    uuid,
+                    KindMessage::parse(&value).unwrap_or(KindMessage::Message),
+                )
+            })
+        },
+        map_view::find_map_field_numbers(items)
+            .into_iter()
+            .map(|field_number| {
+                let mut item_path = path.to_vec();
+                item_path.push(field_number);
+
+                if decoding.map_views.contains_key(&item_path) {
+                    empty()
+                } else {
+                    button! {
+                        C!["im_button_size"],
+                        format!("View field {} as map", field_number),
+                        ev(Ev::Click, move |_| UiMessage::ViewAsMap(item_path))
+                    }
+                }
+            })
+            .collect::<Vec<_>>(),
+        match decoding.get_suggested_message_kind(scope, path, items) {
+            Some(suggested) if suggested != kind => button! {
+                C!["im_button_size"],
+                format!("As {}?", suggested.to_string()),
+                ev(Ev::Click, move |_| UiMessage::SelectMessageKind(uuid, suggested))
+            },
+            _ => empty(),
+        },
+    ]
+}
+
+pub fn view_action_buttons(
+    decoding: &Decoding,
+    decoding_state: &DecodingState,
+    path: &[u128],
+    scope: SchemaScope,
+) -> Node<UiMessage> {
     div![match decoding_state {
-        DecodingState::Varint { uuid, .. } => {
+        DecodingState::Varint {
+            uuid, kind, value, ..
+        } => {
             let uuid = *uuid;
             div![
                 button! {C!["im_button_size"],"Toggle", ev(Ev::Click, move |_| UiMessage::Toggle(uuid))},
+                view_kind_select(
+                    KindVarint::all(),
+                    *kind,
+                    KindVarint::to_string,
+                    move |value| {
+                        UiMessage::SelectVarintKind(
+                            uuid,
+                            KindVarint::parse(&value).unwrap_or(KindVarint::Unsigned),
+                        )
+                    }
+                ),
+                match KindVarint::suggest_timestamp_unit(*value) {
+                    Some(unit) if !matches!(kind, KindVarint::Timestamp(_)) => {
+                        view_timestamp_suggestion(
+                            unit,
+                            UiMessage::SelectVarintKind(uuid, KindVarint::Timestamp(unit)),
+                        )
+                    }
+                    _ => empty(),
+                },
+                view_number_format_select(decoding, decoding_state, path),
             ]
         }
 
-        DecodingState::Fixed64 { uuid, .. } => {
+        DecodingState::Fixed64 {
+            uuid, kind, buffer, ..
+        } => {
             let uuid = *uuid;
             div![
                 button! {C!["im_button_size"],"Toggle", ev(Ev::Click, move |_| UiMessage::Toggle(uuid))},
+                view_kind_select(Kind64::all(), *kind, Kind64::to_string, move |value| {
+                    UiMessage::SelectKind64(uuid, Kind64::parse(&value).unwrap_or(Kind64::Buffer))
+                }),
+                match Kind64::suggest_timestamp_unit(buffer) {
+                    Some(unit) if !matches!(kind, Kind64::Timestamp(_)) => {
+                        view_timestamp_suggestion(
+                            unit,
+                            UiMessage::SelectKind64(uuid, Kind64::Timestamp(unit)),
+                        )
+                    }
+                    _ => empty(),
+                },
+                view_number_format_select(decoding, decoding_state, path),
             ]
         }
 
-        DecodingState::Fixed32 { uuid, .. } => {
+        DecodingState::Fixed32 {
+            uuid, kind, buffer, ..
+        } => {
             let uuid = *uuid;
             div![
                 button! {C!["im_button_size"],"Toggle", ev(Ev::Click, move |_| UiMessage::Toggle(uuid))},
+                view_kind_select(Kind32::all(), *kind, Kind32::to_string, move |value| {
+                    UiMessage::SelectKind32(uuid, Kind32::parse(&value).unwrap_or(Kind32::Buffer))
+                }),
+                match Kind32::suggest_timestamp_unit(buffer) {
+                    Some(unit) if !matches!(kind, Kind32::Timestamp(_)) => {
+                        view_timestamp_suggestion(
+                            unit,
+                            UiMessage::SelectKind32(uuid, Kind32::Timestamp(unit)),
+                        )
+                    }
+                    _ => empty(),
+                },
+                view_number_format_select(decoding, decoding_state, path),
             ]
         }
 
-        DecodingState::Utf8String { .. } => {
-            empty()
+        DecodingState::Utf8String { value, uuid, .. } => {
+            let uuid = *uuid;
+
+            div![
+                match EmbeddedFormat::detect(value) {
+                    Some(embedded_format) => {
+                        let path = path.to_vec();
+
+                        if decoding.plain_text_views.contains(&path) {
+                            button! {
+                                C!["im_button_size"],
+                                format!("View as {}", embedded_format.to_string()),
+                                ev(Ev::Click, move |_| UiMessage::ViewAsEmbeddedFormat(path))
+                            }
+                        } else {
+                            button! {
+                                C!["im_button_size"],
+                                "View as text",
+                                ev(Ev::Click, move |_| UiMessage::ViewAsPlainText(path))
+                            }
+                        }
+                    }
+                    None => empty(),
+                },
+                match StringEncoding::suggest(value) {
+                    Some(encoding) => button! {
+                        C!["im_button_size"],
+                        format!("Nested message in {}?", encoding.to_string()),
+                        ev(Ev::Click, move |_| UiMessage::DecodeStringAsMessage(uuid, encoding))
+                    },
+                    None => empty(),
+                },
+                StringEncoding::all()
+                    .iter()
+                    .copied()
+                    .filter(|encoding| encoding.decode(value).is_some())
+                    .map(|encoding| {
+                        button! {
+                            C!["im_button_size"],
+                            format!("Decode {} as bytes", encoding.to_string()),
+                            ev(Ev::Click, move |_| UiMessage::DecodeStringAsBytes(uuid, encoding))
+                        }
+                    }),
+            ]
         }
 
-        DecodingState::Chunk { uuid, .. } => {
+        DecodingState::Chunk { uuid, buffer, .. } => {
             let uuid = *uuid;
 
             div![
                 button! {C!["im_button_size"], "Message", ev(Ev::Click, move |_| UiMessage::DecodeChunkAsMessage(uuid)) },
                 button! {C!["im_button_size"], "UTF-8", ev(Ev::Click, move |_| UiMessage::DecodeChunkAsUtf8String(uuid)) },
+                view_entropy(buffer),
+                get_fitting_bytes_kinds(buffer).into_iter().map(|kind| {
+                    button! {
+                        C!["im_button_size"],
+                        kind.to_string().to_uppercase(),
+                        ev(Ev::Click, move |_| UiMessage::DecodeChunkAsBytes(uuid, kind))
+                    }
+                }),
+            ]
+        }
+
+        DecodingState::Bytes {
+            uuid, buffer, kind, ..
+        } => {
+            let uuid = *uuid;
+
+            div![
+                button! {C!["im_button_size"], "Message", ev(Ev::Click, move |_| UiMessage::DecodeChunkAsMessage(uuid)) },
+                button! {C!["im_button_size"], "Chunk", ev(Ev::Click, move |_| UiMessage::DecodeBytesAsChunk(uuid)) },
+                view_kind_select(
+                    &get_fitting_bytes_kinds(buffer),
+                    *kind,
+                    KindBytes::to_string,
+                    move |value| {
+                        UiMessage::DecodeChunkAsBytes(
+                            uuid,
+                            KindBytes::parse(&value).unwrap_or(KindBytes::Hex),
+                        )
+                    },
+                ),
             ]
         }
 
         DecodingState::Message { .. } => {
-            empty()
+            view_message_kind_select(decoding, decoding_state, path, scope)
         }
     }]
 }
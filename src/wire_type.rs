@@ -1,4 +1,4 @@
-#[derive(Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum WireType {
     Varint = 0,
     Size64 = 1,
@@ -22,3 +22,17 @@ impl From<u128> for WireType {
         }
     }
 }
+
+impl WireType {
+    pub fn to_string(self) -> &'static str {
+        match self {
+            WireType::Varint => "varint",
+            WireType::Size64 => "fixed64",
+            WireType::LengthDelimited => "length-delimited",
+            WireType::StartGroup => "start group",
+            WireType::EndGroup => "end group",
+            WireType::Size32 => "fixed32",
+            WireType::Invalid => "invalid",
+        }
+    }
+}